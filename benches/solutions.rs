@@ -0,0 +1,92 @@
+use std::{hint::black_box, iter};
+
+use advent_of_code_2022::{
+   input::{default_input_path, read_input},
+   registry::SOLUTIONS,
+   solution::Part,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// How many times larger than the real input the synthetic inputs are.
+const SCALE: usize = 8;
+
+/// Builds a larger input for a day out of its real input, keeping it valid
+/// for both parts. Returns `None` for days whose answers don't scale with the
+/// input (day 10's CRT only has room for 240 cycles).
+fn scale_input(day: u8, input: &str, scale: usize) -> Option<String> {
+   let repeat_lines = |input: &str| vec![input.trim_end(); scale].join("\n");
+
+   match day {
+      1 => Some(vec![input.trim_end(); scale].join("\n\n")),
+      2 | 3 | 4 | 7 | 9 => Some(repeat_lines(input)),
+
+      // Undo every move straight after making it, so that the stacks
+      // never run out of crates no matter how often the moves repeat.
+      5 => {
+         let (diagram, moves) = input.split_once("\n\n")?;
+         let undo_moves = moves
+            .lines()
+            .rev()
+            .map(|line| {
+               let tokens: Vec<_> = line.split(' ').collect();
+               format!("move {} from {} to {}", tokens[1], tokens[5], tokens[3])
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+         let round_trip = format!("{}\n{}", moves.trim_end(), undo_moves);
+         Some(format!("{}\n\n{}", diagram, repeat_lines(&round_trip)))
+      }
+
+      // A run of three letters never contains a marker, so the search has
+      // to scan past all of it before reaching the real buffer.
+      6 => Some("abc".repeat(input.len() * scale / 3) + input),
+
+      // Tile the grid so that both of its sides grow.
+      8 => {
+         let side = (scale as f64).sqrt().ceil() as usize;
+         let rows = input
+            .lines()
+            .map(|row| row.repeat(side))
+            .collect::<Vec<_>>();
+         Some(vec![rows.join("\n"); side].join("\n"))
+      }
+
+      _ => None,
+   }
+}
+
+fn bench_solutions(c: &mut Criterion) {
+   for solution in SOLUTIONS {
+      let day = solution.day();
+      let input = read_input(&default_input_path(day)).unwrap();
+
+      let inputs = iter::once(("real", input.clone()))
+         .chain(scale_input(day, &input, SCALE).map(|input| ("scaled", input)));
+
+      let mut group = c.benchmark_group(format!("day{:02}", day));
+
+      for (name, input) in inputs {
+         group.throughput(Throughput::Bytes(input.len() as u64));
+
+         group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+            b.iter(|| solution.parse(black_box(input)).unwrap())
+         });
+
+         let parsed = solution.parse(&input).unwrap();
+         for part in Part::ALL {
+            let id = BenchmarkId::new(format!("part{}", part), name);
+            group.bench_function(id, |b| b.iter(|| parsed.solve(black_box(part))));
+         }
+      }
+
+      group.finish();
+   }
+}
+
+criterion_group! {
+   name = benches;
+   config = Criterion::default().sample_size(10);
+   targets = bench_solutions
+}
+criterion_main!(benches);
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day01::Day01, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day01::parse(input) {
+      Day01::part_one(&parsed);
+      Day01::part_two(&parsed);
+   }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day02::Day02, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day02::parse(input) {
+      Day02::part_one(&parsed);
+      Day02::part_two(&parsed);
+   }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day03::Day03, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day03::parse(input) {
+      Day03::part_one(&parsed);
+      Day03::part_two(&parsed);
+   }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day04::Day04, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day04::parse(input) {
+      Day04::part_one(&parsed);
+      Day04::part_two(&parsed);
+   }
+});
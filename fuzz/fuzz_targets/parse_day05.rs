@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day05::Day05, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day05::parse(input) {
+      Day05::part_one(&parsed);
+      Day05::part_two(&parsed);
+   }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day06::Day06, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day06::parse(input) {
+      Day06::part_one(&parsed);
+      Day06::part_two(&parsed);
+   }
+});
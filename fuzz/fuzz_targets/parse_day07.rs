@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day07::Day07, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day07::parse(input) {
+      Day07::part_one(&parsed);
+      Day07::part_two(&parsed);
+   }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day08::Day08, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day08::parse(input) {
+      Day08::part_one(&parsed);
+      Day08::part_two(&parsed);
+   }
+});
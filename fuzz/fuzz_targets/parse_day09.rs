@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day09::Day09, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day09::parse(input) {
+      Day09::part_one(&parsed);
+      Day09::part_two(&parsed);
+   }
+});
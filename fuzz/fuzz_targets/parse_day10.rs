@@ -0,0 +1,11 @@
+#![no_main]
+
+use advent_of_code_2022::{day10::Day10, solution::Solution};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+   if let Ok(parsed) = Day10::parse(input) {
+      Day10::part_one(&parsed);
+      Day10::part_two(&parsed);
+   }
+});
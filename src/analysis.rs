@@ -0,0 +1,92 @@
+//! Looks into a day's input rather than solving it: statistics for day 1 and
+//! the strategy guide decoder for day 2.
+
+use std::path::Path;
+
+use crate::{
+   day01::{top_elves, Stats},
+   day02::{Day02, Decoding, Score},
+   input::{default_input_path, open_input},
+   runner::Format,
+   solution::Solution,
+};
+
+/// Prints statistics about a day's input, or only the `top` elves carrying
+/// the most calories. Only day 1 is supported.
+pub fn stats(
+   day: u8,
+   input: Option<&Path>,
+   buckets: usize,
+   top: Option<usize>,
+   format: Format,
+) -> Result<(), String> {
+   if day != 1 {
+      return Err(format!(
+         "there are no statistics for day {}, only day 1",
+         day
+      ));
+   }
+
+   if format == Format::Csv {
+      return Err("statistics can only be printed as text or json".to_owned());
+   }
+
+   let path = input.map_or_else(|| default_input_path(day), Path::to_owned);
+
+   // The top elves are found in a single pass, keeping only `top` of them in
+   // memory, so this works on inputs of any size.
+   if let Some(k) = top {
+      let elves = top_elves(open_input(&path)?, k)
+         .map_err(|error| format!("{}: {}", path.display(), error))?;
+
+      match format {
+         Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&elves).expect("elves are always serializable")
+         ),
+         _ => {
+            for (rank, elf) in elves.iter().enumerate() {
+               println!(
+                  "{:>3}. elf {}: {} calories",
+                  rank + 1,
+                  elf.index,
+                  elf.calories
+               );
+            }
+         }
+      }
+
+      return Ok(());
+   }
+
+   let stats = Stats::from_path(&path, buckets)?;
+
+   match format {
+      Format::Json => println!("{}", stats.to_json()),
+      _ => print!("{}", stats),
+   }
+
+   Ok(())
+}
+
+/// Prints the score of the day 2 strategy guide under every reading of X, Y
+/// and Z, and which readings give the observed score, if there is one.
+pub fn decode(input: Option<&Path>, observed: Option<Score>) -> Result<(), String> {
+   let path = input.map_or_else(|| default_input_path(Day02::DAY), Path::to_owned);
+   let decoding = Decoding::from_path(&path)?;
+   print!("{}", decoding);
+
+   if let Some(observed) = observed {
+      let consistent = decoding.consistent_with(observed);
+      if consistent.is_empty() {
+         return Err(format!("no reading of the guide scores {}", observed));
+      }
+
+      println!("\nReadings that score {}:", observed);
+      for interpretation in consistent {
+         println!("  {}", interpretation);
+      }
+   }
+
+   Ok(())
+}
@@ -0,0 +1,97 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use toml::{Table, Value};
+
+use crate::solution::Part;
+
+/// Where the expected answers for the inputs in `input/` are stored.
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+/// The expected answer for each day and part, keyed as `[dayNN] partN = ...`.
+#[derive(Debug, Default)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+/// How a computed answer compares to the expected one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict<'a> {
+   Pass,
+   Fail { expected: &'a str },
+   Unknown,
+}
+
+impl Verdict<'_> {
+   pub fn as_str(&self) -> &'static str {
+      match self {
+         Verdict::Pass => "pass",
+         Verdict::Fail { .. } => "fail",
+         Verdict::Unknown => "unknown",
+      }
+   }
+}
+
+impl Answers {
+   pub fn load(path: &Path) -> Result<Self, String> {
+      let text = fs::read_to_string(path)
+         .map_err(|error| format!("could not read {}: {}", path.display(), error))?;
+
+      Self::parse(&text).map_err(|error| format!("could not parse {}: {}", path.display(), error))
+   }
+
+   pub fn parse(text: &str) -> Result<Self, String> {
+      let table = text.parse::<Table>().map_err(|error| error.to_string())?;
+      let mut answers = BTreeMap::new();
+
+      for (section, parts) in table {
+         let day = section
+            .strip_prefix("day")
+            .and_then(|day| day.parse::<u8>().ok())
+            .ok_or_else(|| format!("`[{}]` is not a day (expected e.g. `[day01]`)", section))?;
+
+         let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("`{}` should be a table of answers", section))?;
+
+         for (key, value) in parts {
+            let part = match key.as_str() {
+               "part1" => Part::One,
+               "part2" => Part::Two,
+               _ => {
+                  return Err(format!(
+                     "`{}.{}` is not a part (expected part1 or part2)",
+                     section, key
+                  ))
+               }
+            };
+
+            let answer = match value {
+               Value::String(answer) => answer.clone(),
+               Value::Integer(answer) => answer.to_string(),
+               _ => {
+                  return Err(format!(
+                     "`{}.{}` should be a string or an integer",
+                     section, key
+                  ))
+               }
+            };
+
+            answers.insert((day, part), answer);
+         }
+      }
+
+      Ok(Answers(answers))
+   }
+
+   pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+      self.0.get(&(day, part)).map(String::as_str)
+   }
+
+   /// Compares a computed answer against the expected one. Trailing
+   /// whitespace is ignored, since multi-line answers usually end in a newline.
+   pub fn check(&self, day: u8, part: Part, answer: &str) -> Verdict<'_> {
+      match self.get(day, part) {
+         None => Verdict::Unknown,
+         Some(expected) if expected.trim_end() == answer.trim_end() => Verdict::Pass,
+         Some(expected) => Verdict::Fail { expected },
+      }
+   }
+}
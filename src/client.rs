@@ -0,0 +1,252 @@
+//! A small, rate-limited client for the Advent of Code website.
+
+use std::{
+   env, fs,
+   path::{Path, PathBuf},
+   thread,
+   time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ureq::Agent;
+
+/// Where the session token and other settings are read from.
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+/// Environment variables that take precedence over the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The shortest time allowed between two requests, unless configured
+/// otherwise, to go easy on the website.
+pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Remembers when the last request was made, so that the rate limit also
+/// applies across separate runs.
+pub const DEFAULT_LAST_REQUEST_PATH: &str = "target/aoc-last-request";
+
+const USER_AGENT: &str = "github.com/NelsonTran21/advent-of-code-2022";
+
+#[derive(Clone, Debug)]
+pub struct Config {
+   pub base_url: String,
+   pub session: Option<String>,
+   pub min_interval: Duration,
+   pub last_request_path: PathBuf,
+}
+
+impl Default for Config {
+   fn default() -> Self {
+      Config {
+         base_url: DEFAULT_BASE_URL.to_owned(),
+         session: None,
+         min_interval: DEFAULT_MIN_INTERVAL,
+         last_request_path: PathBuf::from(DEFAULT_LAST_REQUEST_PATH),
+      }
+   }
+}
+
+impl Config {
+   /// Reads the config file, if there is one, then applies any overrides
+   /// from the environment.
+   pub fn load(path: &Path) -> Result<Self, String> {
+      let mut config = match fs::read_to_string(path) {
+         Ok(contents) => Config::parse(&contents)
+            .map_err(|message| format!("could not parse {}: {}", path.display(), message))?,
+         Err(_) => Config::default(),
+      };
+
+      if let Ok(session) = env::var(SESSION_VAR) {
+         config.session = Some(session);
+      }
+
+      if let Ok(base_url) = env::var(BASE_URL_VAR) {
+         config.base_url = base_url;
+      }
+
+      Ok(config)
+   }
+
+   /// Parses a config file such as:
+   ///
+   /// ```toml
+   /// session = "53616c7465645f5f..."
+   /// base_url = "https://adventofcode.com"
+   /// min_interval_secs = 5
+   /// ```
+   pub fn parse(contents: &str) -> Result<Self, String> {
+      let table = contents
+         .parse::<toml::Table>()
+         .map_err(|error| error.message().to_owned())?;
+
+      let mut config = Config::default();
+
+      for (key, value) in &table {
+         let expected = |kind: &str| format!("expected `{}` to be {}", key, kind);
+
+         match key.as_str() {
+            "session" => {
+               config.session = Some(
+                  value
+                     .as_str()
+                     .ok_or_else(|| expected("a string"))?
+                     .to_owned(),
+               )
+            }
+            "base_url" => {
+               config.base_url = value
+                  .as_str()
+                  .ok_or_else(|| expected("a string"))?
+                  .to_owned()
+            }
+            "min_interval_secs" => {
+               let seconds = value
+                  .as_integer()
+                  .and_then(|seconds| u64::try_from(seconds).ok())
+                  .ok_or_else(|| expected("a number of seconds"))?;
+               config.min_interval = Duration::from_secs(seconds);
+            }
+            _ => return Err(format!("unknown setting `{}`", key)),
+         }
+      }
+
+      Ok(config)
+   }
+}
+
+pub struct Client {
+   config: Config,
+   agent: Agent,
+}
+
+impl Client {
+   pub fn new(config: Config) -> Self {
+      let agent = Agent::config_builder()
+         .http_status_as_error(false)
+         .user_agent(USER_AGENT)
+         .build()
+         .into();
+
+      Client { config, agent }
+   }
+
+   /// The session token is only needed once a request is made.
+   fn session(&self) -> Result<&str, String> {
+      self.config.session.as_deref().ok_or_else(|| {
+         format!(
+            "no session token: set {} or `session` in {}",
+            SESSION_VAR, CONFIG_PATH
+         )
+      })
+   }
+
+   fn url(&self, path: &str) -> String {
+      format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+   }
+
+   /// Fetches a page, returning its body.
+   pub fn get(&self, path: &str) -> Result<String, String> {
+      let url = self.url(path);
+      let session = self.session()?;
+      self.wait_for_rate_limit()?;
+
+      let response = self
+         .agent
+         .get(&url)
+         .header("Cookie", &format!("session={}", session))
+         .call();
+
+      read_response(&url, response)
+   }
+
+   /// Submits a form, returning the body of the response.
+   pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String, String> {
+      let url = self.url(path);
+      let session = self.session()?;
+      self.wait_for_rate_limit()?;
+
+      let response = self
+         .agent
+         .post(&url)
+         .header("Cookie", &format!("session={}", session))
+         .send_form(fields.iter().copied());
+
+      read_response(&url, response)
+   }
+
+   /// Waits until enough time has passed since the last request, then
+   /// records the time of this one.
+   fn wait_for_rate_limit(&self) -> Result<(), String> {
+      let path = &self.config.last_request_path;
+      let last_request = fs::read_to_string(path)
+         .ok()
+         .and_then(|millis| millis.trim().parse().ok())
+         .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+      if let Some(last_request) = last_request {
+         let elapsed = SystemTime::now()
+            .duration_since(last_request)
+            .unwrap_or_default();
+
+         if let Some(remaining) = self.config.min_interval.checked_sub(elapsed) {
+            eprintln!(
+               "waiting {:.1}s before the next request",
+               remaining.as_secs_f64()
+            );
+            thread::sleep(remaining);
+         }
+      }
+
+      let now = SystemTime::now()
+         .duration_since(UNIX_EPOCH)
+         .unwrap_or_default();
+
+      if let Some(parent) = path.parent() {
+         fs::create_dir_all(parent).ok();
+      }
+
+      fs::write(path, now.as_millis().to_string())
+         .map_err(|error| format!("could not write {}: {}", path.display(), error))
+   }
+}
+
+fn read_response(
+   url: &str,
+   response: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+) -> Result<String, String> {
+   let mut response = response.map_err(|error| format!("request to {} failed: {}", url, error))?;
+   let status = response.status();
+
+   let hint = match status.as_u16() {
+      200 => {
+         return response
+            .body_mut()
+            .read_to_string()
+            .map_err(|error| format!("could not read the response from {}: {}", url, error))
+      }
+      400 | 500 => " (is the session token still valid?)",
+      404 => " (has the puzzle unlocked yet?)",
+      _ => "",
+   };
+
+   Err(format!("request to {} failed with {}{}", url, status, hint))
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn parses_the_config_file() {
+      let config = Config::parse(
+         "session = \"abc\"\nbase_url = \"http://localhost:8080\"\nmin_interval_secs = 0\n",
+      )
+      .unwrap();
+
+      assert_eq!(config.session.as_deref(), Some("abc"));
+      assert_eq!(config.base_url, "http://localhost:8080");
+      assert_eq!(config.min_interval, Duration::ZERO);
+      assert!(Config::parse("token = \"abc\"").is_err());
+   }
+}
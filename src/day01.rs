@@ -1,24 +1,1000 @@
-type Calories = u32;
+//! Day 1: Calorie Counting
 
-fn parse_input() -> Vec<Calories> {
-   include_str!("../input/day01.txt")
-      .split("\n\n")
-      .map(|food_bags| {
-         food_bags
-            .lines()
-            .map(|food| food.parse::<Calories>().expect("bad value"))
-            .sum()
+use std::{
+   cmp::Reverse,
+   collections::BinaryHeap,
+   error::Error,
+   fmt,
+   io::{self, BufRead},
+   path::Path,
+};
+
+use serde::Serialize;
+
+use crate::{error::ParseError, input::open_input, solution::Solution};
+
+pub type Calories = u32;
+
+/// An error found while streaming food bags: either the reader failed or
+/// the input is invalid.
+#[derive(Debug)]
+pub enum StreamError {
+   Io(io::Error),
+   Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self {
+         StreamError::Io(error) => write!(f, "could not read the food bags: {}", error),
+         StreamError::Parse(error) => write!(f, "{}", error),
+      }
+   }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+   fn from(error: io::Error) -> Self {
+      StreamError::Io(error)
+   }
+}
+
+impl From<ParseError> for StreamError {
+   fn from(error: ParseError) -> Self {
+      StreamError::Parse(error)
+   }
+}
+
+/// The calories in one elf's food bag, and how many items they add up from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FoodBag {
+   pub calories: Calories,
+   pub items: usize,
+}
+
+/// Reads each food bag from a stream, one bag at a time, so that only the
+/// current line is ever held in memory.
+pub struct FoodBags<R> {
+   reader: R,
+   line: String,
+   line_number: usize,
+   finished: bool,
+}
+
+impl<R: BufRead> FoodBags<R> {
+   pub fn new(reader: R) -> Self {
+      FoodBags {
+         reader,
+         line: String::new(),
+         line_number: 0,
+         finished: false,
+      }
+   }
+
+   /// Reads lines up to the end of the next food bag, passing each item to
+   /// `item` as it is read.
+   fn read_food_bag(
+      &mut self,
+      mut item: impl FnMut(Calories),
+   ) -> Result<Option<FoodBag>, StreamError> {
+      let mut food_bag: Option<FoodBag> = None;
+
+      loop {
+         self.line.clear();
+         if self.reader.read_line(&mut self.line)? == 0 {
+            self.finished = true;
+            return Ok(food_bag);
+         }
+
+         self.line_number += 1;
+         let line = self.line.trim_end_matches(['\n', '\r']);
+
+         // Each elf's food bag is separated from the next by a blank line.
+         if line.is_empty() {
+            match food_bag {
+               Some(_) => return Ok(food_bag),
+               None => continue,
+            }
+         }
+
+         let error =
+            |message: &str| ParseError::new(Day01::DAY, self.line_number, line, line, message);
+
+         let food = line
+            .parse::<Calories>()
+            .map_err(|_| error("expected a calorie count"))?;
+         item(food);
+
+         let food_bag = food_bag.get_or_insert(FoodBag {
+            calories: 0,
+            items: 0,
+         });
+         food_bag.items += 1;
+         food_bag.calories = food_bag
+            .calories
+            .checked_add(food)
+            .ok_or_else(|| error("this food bag holds too many calories to count"))?;
+      }
+   }
+
+   fn next_food_bag(&mut self, item: impl FnMut(Calories)) -> Option<Result<FoodBag, StreamError>> {
+      if self.finished {
+         return None;
+      }
+
+      let food_bag = self.read_food_bag(item);
+      if food_bag.is_err() {
+         self.finished = true;
+      }
+
+      food_bag.transpose()
+   }
+
+   /// Reads each elf with every item in its food bag, rather than just the
+   /// totals.
+   pub fn elves(self) -> Elves<R> {
+      Elves(self)
+   }
+}
+
+impl<R: BufRead> Iterator for FoodBags<R> {
+   type Item = Result<FoodBag, StreamError>;
+
+   fn next(&mut self) -> Option<Self::Item> {
+      self.next_food_bag(|_| ())
+   }
+}
+
+/// Reads each elf and its items from a stream, one food bag at a time.
+pub struct Elves<R>(FoodBags<R>);
+
+impl<R: BufRead> Iterator for Elves<R> {
+   type Item = Result<Elf, StreamError>;
+
+   fn next(&mut self) -> Option<Self::Item> {
+      let mut items = vec![];
+      let food_bag = self.0.next_food_bag(|calories| items.push(calories))?;
+      Some(food_bag.map(|_| Elf { items }))
+   }
+}
+
+/// Unwraps an error from streaming an input that is already in memory,
+/// which can only be invalid, not unreadable.
+fn in_memory(error: StreamError) -> ParseError {
+   match error {
+      StreamError::Parse(error) => error,
+      StreamError::Io(error) => unreachable!("reading from memory failed: {}", error),
+   }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Calories>, ParseError> {
+   FoodBags::new(input.as_bytes())
+      .map(|food_bag| food_bag.map(|food_bag| food_bag.calories))
+      .collect::<Result<_, _>>()
+      .map_err(in_memory)
+}
+
+/// An elf's index and the total calories in its food bag. Elves are indexed
+/// from 0, in the order their food bags appear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ElfTotal {
+   pub calories: Calories,
+   pub index: usize,
+}
+
+/// The `k` elves carrying the most calories, kept in a heap of at most `k`
+/// elves so that any number of elves can be pushed through it. Of elves
+/// carrying the same calories, the first ones are kept.
+pub struct TopElves {
+   k: usize,
+   // A min-heap, so that the elf to drop is always on top. Among elves with
+   // the same calories, the one that came last is dropped first.
+   heap: BinaryHeap<Reverse<(Calories, Reverse<usize>)>>,
+}
+
+impl TopElves {
+   pub fn new(k: usize) -> Self {
+      TopElves {
+         k,
+         heap: BinaryHeap::new(),
+      }
+   }
+
+   pub fn push(&mut self, elf: ElfTotal) {
+      self.heap.push(Reverse((elf.calories, Reverse(elf.index))));
+      if self.heap.len() > self.k {
+         self.heap.pop();
+      }
+   }
+
+   /// The elves kept, the one carrying the most calories first.
+   pub fn into_sorted_vec(self) -> Vec<ElfTotal> {
+      self
+         .heap
+         .into_sorted_vec()
+         .into_iter()
+         .map(|Reverse((calories, Reverse(index)))| ElfTotal { calories, index })
+         .collect()
+   }
+}
+
+impl Extend<ElfTotal> for TopElves {
+   fn extend<I: IntoIterator<Item = ElfTotal>>(&mut self, elves: I) {
+      elves.into_iter().for_each(|elf| self.push(elf));
+   }
+}
+
+/// Finds the `k` elves carrying the most calories in a stream of food bags,
+/// the one carrying the most first, using memory proportional to `k` rather
+/// than to the number of elves.
+pub fn top_elves<R: BufRead>(reader: R, k: usize) -> Result<Vec<ElfTotal>, StreamError> {
+   let mut top = TopElves::new(k);
+
+   for (index, food_bag) in FoodBags::new(reader).enumerate() {
+      top.push(ElfTotal {
+         calories: food_bag?.calories,
+         index,
+      });
+   }
+
+   Ok(top.into_sorted_vec())
+}
+
+/// The percentiles reported by [`Stats`], besides the median.
+pub const PERCENTILES: [u8; 5] = [10, 25, 75, 90, 99];
+
+/// The elves sharing the lowest value of something, e.g. the fewest items.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fewest<T> {
+   pub value: T,
+   pub elves: Vec<usize>,
+}
+
+impl<T: Copy + Ord> Fewest<T> {
+   fn find(values: impl Iterator<Item = T>) -> Option<Self> {
+      let mut fewest: Option<Fewest<T>> = None;
+
+      for (elf, value) in values.enumerate() {
+         match &mut fewest {
+            Some(fewest) if value == fewest.value => fewest.elves.push(elf),
+            Some(fewest) if value > fewest.value => (),
+            _ => {
+               fewest = Some(Fewest {
+                  value,
+                  elves: vec![elf],
+               })
+            }
+         }
+      }
+
+      fewest
+   }
+}
+
+/// The calories below which `percentile` percent of the elves fall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Percentile {
+   pub percentile: u8,
+   pub calories: Calories,
+}
+
+/// How many elves carry between `low` and `high` calories, inclusive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+   pub low: Calories,
+   pub high: Calories,
+   pub elves: usize,
+}
+
+/// Descriptive statistics over the calories carried by each elf.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+   pub count: usize,
+   pub total: u64,
+   pub min: Calories,
+   pub max: Calories,
+   pub mean: f64,
+   pub median: f64,
+   /// The population standard deviation.
+   pub std_dev: f64,
+   /// Each of [`PERCENTILES`], by the nearest-rank method.
+   pub percentiles: Vec<Percentile>,
+   /// Equal-width buckets from `min` to `max`.
+   pub histogram: Vec<Bucket>,
+   pub fewest_items: Fewest<usize>,
+   pub fewest_calories: Fewest<Calories>,
+}
+
+/// The value below which `p` percent of the sorted values fall, by the
+/// nearest-rank method.
+pub fn percentile(sorted: &[Calories], p: u8) -> Calories {
+   let rank = (p as usize * sorted.len()).div_ceil(100);
+   sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Splits `min..=max` into at most `buckets` buckets of equal width and
+/// counts the values in each.
+fn histogram(sorted: &[Calories], buckets: usize) -> Vec<Bucket> {
+   let (min, max) = (sorted[0] as u64, sorted[sorted.len() - 1] as u64);
+   let width = (max - min + 1).div_ceil(buckets.max(1) as u64);
+
+   let mut histogram = (0..=(max - min) / width)
+      .map(|bucket| Bucket {
+         low: (min + bucket * width) as Calories,
+         high: (min + (bucket + 1) * width - 1).min(max) as Calories,
+         elves: 0,
+      })
+      .collect::<Vec<_>>();
+
+   for &calories in sorted {
+      histogram[((calories as u64 - min) / width) as usize].elves += 1;
+   }
+
+   histogram
+}
+
+impl Stats {
+   /// Computes the statistics of the food bags, in `buckets` histogram
+   /// buckets. Returns `None` if there are no food bags.
+   pub fn new(food_bags: &[FoodBag], buckets: usize) -> Option<Self> {
+      if food_bags.is_empty() {
+         return None;
+      }
+
+      let mut sorted = food_bags
+         .iter()
+         .map(|food_bag| food_bag.calories)
+         .collect::<Vec<_>>();
+      sorted.sort_unstable();
+
+      let count = sorted.len();
+      let total = sorted.iter().map(|&calories| calories as u64).sum::<u64>();
+      let mean = total as f64 / count as f64;
+      let variance = sorted
+         .iter()
+         .map(|&calories| (calories as f64 - mean).powi(2))
+         .sum::<f64>()
+         / count as f64;
+
+      let median = match count % 2 {
+         0 => (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0,
+         _ => sorted[count / 2] as f64,
+      };
+
+      Some(Stats {
+         count,
+         total,
+         min: sorted[0],
+         max: sorted[count - 1],
+         mean,
+         median,
+         std_dev: variance.sqrt(),
+         percentiles: PERCENTILES
+            .iter()
+            .map(|&percentile| Percentile {
+               percentile,
+               calories: self::percentile(&sorted, percentile),
+            })
+            .collect(),
+         histogram: histogram(&sorted, buckets),
+         fewest_items: Fewest::find(food_bags.iter().map(|food_bag| food_bag.items))?,
+         fewest_calories: Fewest::find(food_bags.iter().map(|food_bag| food_bag.calories))?,
+      })
+   }
+
+   /// Reads the food bags a line at a time, keeping only each elf's totals,
+   /// and computes their statistics. Returns `None` if there are no food
+   /// bags.
+   pub fn read<R: BufRead>(reader: R, buckets: usize) -> Result<Option<Self>, StreamError> {
+      let food_bags = FoodBags::new(reader).collect::<Result<Vec<_>, _>>()?;
+      Ok(Stats::new(&food_bags, buckets))
+   }
+
+   /// Reads the food bags from a file, or from stdin if the path is `-`, and
+   /// computes their statistics.
+   pub fn from_path(path: &Path, buckets: usize) -> Result<Self, String> {
+      Stats::read(open_input(path)?, buckets)
+         .map_err(|error| format!("{}: {}", path.display(), error))?
+         .ok_or_else(|| format!("{} has no food bags", path.display()))
+   }
+
+   pub fn to_json(&self) -> String {
+      serde_json::to_string_pretty(self).expect("stats are always serializable")
+   }
+}
+
+impl fmt::Display for Stats {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      // Large inputs can have thousands of elves tied for the fewest.
+      let elves = |elves: &[usize]| {
+         let mut list = elves
+            .iter()
+            .take(10)
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+         if elves.len() > 10 {
+            list += &format!(" and {} more", elves.len() - 10);
+         }
+
+         match elves.len() {
+            1 => format!("elf {}", list),
+            _ => format!("elves {}", list),
+         }
+      };
+
+      let percentiles = self
+         .percentiles
+         .iter()
+         .map(|p| format!("p{} {}", p.percentile, p.calories))
+         .collect::<Vec<_>>()
+         .join(", ");
+
+      writeln!(f, "Elves:           {}", self.count)?;
+      writeln!(f, "Total calories:  {}", self.total)?;
+      writeln!(f, "Min / max:       {} / {}", self.min, self.max)?;
+      writeln!(f, "Mean:            {:.1}", self.mean)?;
+      writeln!(f, "Median:          {:.1}", self.median)?;
+      writeln!(f, "Std deviation:   {:.1}", self.std_dev)?;
+      writeln!(f, "Percentiles:     {}", percentiles)?;
+      writeln!(
+         f,
+         "Fewest items:    {} ({})",
+         self.fewest_items.value,
+         elves(&self.fewest_items.elves)
+      )?;
+      writeln!(
+         f,
+         "Fewest calories: {} ({})",
+         self.fewest_calories.value,
+         elves(&self.fewest_calories.elves)
+      )?;
+
+      // Scale the bars so that the fullest bucket is 40 characters wide.
+      let fullest = self.histogram.iter().map(|bucket| bucket.elves).max();
+      let high_width = self.max.to_string().len();
+
+      writeln!(f, "\nHistogram:")?;
+      for bucket in &self.histogram {
+         let bar = (bucket.elves * 40).div_ceil(fullest.unwrap_or(1).max(1));
+         writeln!(
+            f,
+            "{:>width$} - {:>width$} | {} {}",
+            bucket.low,
+            bucket.high,
+            "#".repeat(bar),
+            bucket.elves,
+            width = high_width
+         )?;
+      }
+
+      Ok(())
+   }
+}
+
+/// Where an item is: the index of the elf carrying it, and its index in
+/// that elf's food bag, both from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemPosition {
+   pub elf: usize,
+   pub item: usize,
+}
+
+/// An elf and every item in its food bag, in input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+   pub items: Vec<Calories>,
+}
+
+impl Elf {
+   /// The calories of every item. Unlike a [`FoodBag`], this cannot
+   /// overflow, even after items are moved around.
+   pub fn total(&self) -> u64 {
+      self.items.iter().map(|&calories| calories as u64).sum()
+   }
+}
+
+/// Moves the item at `item` to the end of elf `to`'s food bag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+   pub item: ItemPosition,
+   pub calories: Calories,
+   pub to: usize,
+}
+
+/// Moves that bring every elf under a calorie limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapPlan {
+   pub moves: Vec<Move>,
+   /// No plan can move fewer items than this, so the plan is minimal if it
+   /// moves exactly this many.
+   pub lower_bound: usize,
+}
+
+impl CapPlan {
+   pub fn is_minimal(&self) -> bool {
+      self.moves.len() == self.lower_bound
+   }
+}
+
+/// Every elf's food bag, keeping the individual items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+   pub elves: Vec<Elf>,
+}
+
+/// The elf with the most room left that can still take `calories` without
+/// going over `limit`, i.e. the best fit.
+fn best_fit(totals: &[u64], calories: u64, limit: u64) -> Option<usize> {
+   (0..totals.len())
+      .filter(|&elf| totals[elf] + calories <= limit)
+      .max_by_key(|&elf| (totals[elf], Reverse(elf)))
+}
+
+impl Inventory {
+   pub fn parse(input: &str) -> Result<Self, ParseError> {
+      Inventory::read(input.as_bytes()).map_err(in_memory)
+   }
+
+   pub fn read<R: BufRead>(reader: R) -> Result<Self, StreamError> {
+      Ok(Inventory {
+         elves: FoodBags::new(reader).elves().collect::<Result<_, _>>()?,
+      })
+   }
+
+   /// Every item, along with its position.
+   pub fn items(&self) -> impl Iterator<Item = (ItemPosition, Calories)> + '_ {
+      self.elves.iter().enumerate().flat_map(|(elf, food_bag)| {
+         food_bag
+            .items
+            .iter()
+            .enumerate()
+            .map(move |(item, &calories)| (ItemPosition { elf, item }, calories))
       })
-      .collect()
+   }
+
+   /// The single heaviest item, or the first of them if several are equally
+   /// heavy.
+   pub fn heaviest_item(&self) -> Option<(ItemPosition, Calories)> {
+      self
+         .items()
+         .max_by_key(|&(position, calories)| (calories, Reverse(position)))
+   }
+
+   /// Redistributes every item between the same number of elves so that
+   /// their totals are as even as possible.
+   ///
+   /// An exact answer is NP-hard, so this assigns the items from heaviest to
+   /// lightest, each to the elf carrying the least so far. The heaviest total
+   /// is then at most 4/3 of the best possible.
+   pub fn rebalance(&self) -> Inventory {
+      let mut items = self
+         .items()
+         .map(|(_, calories)| calories)
+         .collect::<Vec<_>>();
+      items.sort_unstable_by(|a, b| b.cmp(a));
+
+      let mut elves = vec![Elf { items: vec![] }; self.elves.len()];
+
+      // A min-heap of each elf's total, so that the lightest elf is always on
+      // top. Among elves carrying the same, the first one is on top.
+      let mut lightest = (0..elves.len())
+         .map(|elf| Reverse((0u64, elf)))
+         .collect::<BinaryHeap<_>>();
+
+      for calories in items {
+         let Reverse((total, elf)) = lightest.pop().expect("items belong to elves");
+
+         elves[elf].items.push(calories);
+         lightest.push(Reverse((total + calories as u64, elf)));
+      }
+
+      Inventory { elves }
+   }
+
+   /// Applies the moves, which must all refer to positions in this
+   /// inventory.
+   pub fn apply(&self, moves: &[Move]) -> Inventory {
+      let mut elves = self.elves.clone();
+      let mut moved = moves.iter().map(|m| m.item).collect::<Vec<_>>();
+
+      // Remove the items back to front so that the positions stay valid.
+      moved.sort_unstable_by(|a, b| b.cmp(a));
+      for position in moved {
+         elves[position.elf].items.remove(position.item);
+      }
+
+      for m in moves {
+         elves[m.to].items.push(m.calories);
+      }
+
+      Inventory { elves }
+   }
+
+   /// Finds a way to move as few items as possible so that no elf carries
+   /// more than `limit` calories.
+   ///
+   /// Each elf over the limit has to give up at least as many items as its
+   /// heaviest items take to bring it under, which gives a lower bound. If
+   /// those items fit in the room left by the other elves, the plan is
+   /// minimal. Otherwise every item is repacked, heaviest first, keeping
+   /// items where they are whenever they fit; that plan may not be minimal.
+   pub fn moves_to_cap(&self, limit: u64) -> Result<CapPlan, String> {
+      if let Some((position, calories)) = self.heaviest_item() {
+         if calories as u64 > limit {
+            return Err(format!(
+               "elf {} carries an item of {} calories, more than the limit on its own",
+               position.elf, calories
+            ));
+         }
+      }
+
+      let total = self.elves.iter().map(Elf::total).sum::<u64>();
+      if total > limit.saturating_mul(self.elves.len() as u64) {
+         return Err(format!(
+            "the elves carry {} calories, more than {} elves can at {} each",
+            total,
+            self.elves.len(),
+            limit
+         ));
+      }
+
+      // Take the heaviest items from each elf over the limit.
+      let mut totals = self.elves.iter().map(Elf::total).collect::<Vec<_>>();
+      let mut freed = vec![];
+
+      for (elf, food_bag) in self.elves.iter().enumerate() {
+         let mut items = food_bag
+            .items
+            .iter()
+            .copied()
+            .enumerate()
+            .collect::<Vec<_>>();
+         items.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+         for (item, calories) in items {
+            if totals[elf] <= limit {
+               break;
+            }
+
+            totals[elf] -= calories as u64;
+            freed.push((ItemPosition { elf, item }, calories));
+         }
+      }
+
+      let lower_bound = freed.len();
+      freed.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+      let mut moves = vec![];
+      for (item, calories) in freed {
+         match best_fit(&totals, calories as u64, limit) {
+            Some(to) => {
+               totals[to] += calories as u64;
+               moves.push(Move { item, calories, to });
+            }
+            None => {
+               return self
+                  .repack(limit)
+                  .map(|moves| CapPlan { moves, lower_bound })
+                  .ok_or_else(|| {
+                     format!(
+                        "could not find a way to fit the items under {} calories",
+                        limit
+                     )
+                  })
+            }
+         }
+      }
+
+      Ok(CapPlan { moves, lower_bound })
+   }
+
+   /// Packs every item under the limit, heaviest first, leaving items with
+   /// their elf whenever there is room.
+   fn repack(&self, limit: u64) -> Option<Vec<Move>> {
+      let mut items = self.items().collect::<Vec<_>>();
+      items.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+      let mut totals = vec![0u64; self.elves.len()];
+      let mut moves = vec![];
+
+      for (item, calories) in items {
+         let weight = calories as u64;
+         if totals[item.elf] + weight <= limit {
+            totals[item.elf] += weight;
+            continue;
+         }
+
+         let to = best_fit(&totals, weight, limit)?;
+         totals[to] += weight;
+         moves.push(Move { item, calories, to });
+      }
+
+      Some(moves)
+   }
 }
 
-pub fn solve_part_one() -> Calories {
-   let food_bags = parse_input();
-   food_bags.into_iter().max().expect("no food bags")
+pub struct Day01;
+
+impl Solution for Day01 {
+   const DAY: u8 = 1;
+
+   type Input = Vec<Calories>;
+   type PartOne = Calories;
+   type PartTwo = u64;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(food_bags: &Self::Input) -> Calories {
+      food_bags.iter().copied().max().unwrap_or(0)
+   }
+
+   /// The top three elves can carry more calories between them than a
+   /// single food bag can hold.
+   fn part_two(food_bags: &Self::Input) -> u64 {
+      let mut top = TopElves::new(3);
+      top.extend(
+         food_bags
+            .iter()
+            .enumerate()
+            .map(|(index, &calories)| ElfTotal { calories, index }),
+      );
+
+      top.into_sorted_vec()
+         .iter()
+         .map(|elf| elf.calories as u64)
+         .sum()
+   }
 }
 
-pub fn solve_part_two() -> Calories {
-   let mut food_bags = parse_input();
-   food_bags.sort_by(|a, b| b.cmp(a));
-   food_bags.into_iter().take(3).sum()
+#[cfg(test)]
+mod tests {
+   use proptest::prelude::*;
+
+   use super::*;
+
+   const EXAMPLE: &str = "\
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+   #[test]
+   fn part_one_example() {
+      let food_bags = Day01::parse(EXAMPLE).unwrap();
+      assert_eq!(Day01::part_one(&food_bags), 24000);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let food_bags = Day01::parse(EXAMPLE).unwrap();
+      assert_eq!(Day01::part_two(&food_bags), 45000);
+   }
+
+   #[test]
+   fn no_food_bags_carry_no_calories() {
+      let food_bags = Day01::parse("").unwrap();
+      assert_eq!(Day01::part_one(&food_bags), 0);
+      assert_eq!(Day01::part_two(&food_bags), 0);
+   }
+
+   #[test]
+   fn sums_the_top_elves_without_overflowing() {
+      let food_bags = Day01::parse("4000000000\n\n4000000000\n\n4000000000").unwrap();
+      assert_eq!(Day01::part_two(&food_bags), 12000000000);
+   }
+
+   #[test]
+   fn rejects_food_bags_that_overflow() {
+      let error = Day01::parse("4294967295\n1\n\n1").unwrap_err();
+      assert_eq!((error.line, error.text.as_str()), (2, "1"));
+   }
+
+   #[test]
+   fn streams_the_top_elves_with_their_indices() {
+      let top = top_elves(EXAMPLE.as_bytes(), 3).unwrap();
+      assert_eq!(
+         top,
+         [
+            ElfTotal {
+               calories: 24000,
+               index: 3
+            },
+            ElfTotal {
+               calories: 11000,
+               index: 2
+            },
+            ElfTotal {
+               calories: 10000,
+               index: 4
+            },
+         ]
+      );
+
+      assert!(top_elves(EXAMPLE.as_bytes(), 0).unwrap().is_empty());
+      assert_eq!(top_elves(EXAMPLE.as_bytes(), 10).unwrap().len(), 5);
+
+      let windows = EXAMPLE.replace('\n', "\r\n");
+      assert_eq!(top_elves(windows.as_bytes(), 3).unwrap(), top);
+   }
+
+   #[test]
+   fn stops_streaming_at_the_first_error() {
+      let mut food_bags = FoodBags::new("1000\n\nlots\n\n2000\n".as_bytes());
+      assert_eq!(
+         food_bags.next().unwrap().unwrap(),
+         FoodBag {
+            calories: 1000,
+            items: 1
+         }
+      );
+
+      match food_bags.next() {
+         Some(Err(StreamError::Parse(error))) => assert_eq!(error.line, 3),
+         other => panic!("expected a parse error, got {:?}", other),
+      }
+
+      assert!(food_bags.next().is_none());
+   }
+
+   #[test]
+   fn describes_the_example_elves() {
+      let stats = Stats::read(EXAMPLE.as_bytes(), 3).unwrap().unwrap();
+
+      assert_eq!((stats.count, stats.total), (5, 55000));
+      assert_eq!((stats.min, stats.max), (4000, 24000));
+      assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+      assert!((stats.std_dev - 6985.7).abs() < 0.1, "{}", stats.std_dev);
+      let percentiles = stats
+         .percentiles
+         .iter()
+         .map(|p| (p.percentile, p.calories))
+         .collect::<Vec<_>>();
+      assert_eq!(
+         percentiles,
+         [
+            (10, 4000),
+            (25, 6000),
+            (75, 11000),
+            (90, 24000),
+            (99, 24000)
+         ]
+      );
+
+      let buckets = stats
+         .histogram
+         .iter()
+         .map(|bucket| (bucket.low, bucket.high, bucket.elves))
+         .collect::<Vec<_>>();
+      assert_eq!(
+         buckets,
+         [(4000, 10666, 3), (10667, 17333, 1), (17334, 24000, 1)]
+      );
+
+      assert_eq!(
+         stats.fewest_items,
+         Fewest {
+            value: 1,
+            elves: vec![1, 4]
+         }
+      );
+      assert_eq!(
+         stats.fewest_calories,
+         Fewest {
+            value: 4000,
+            elves: vec![1]
+         }
+      );
+
+      assert_eq!(Stats::read("".as_bytes(), 10).unwrap(), None);
+   }
+
+   #[test]
+   fn finds_the_heaviest_item() {
+      let inventory = Inventory::parse(EXAMPLE).unwrap();
+      assert_eq!(
+         inventory.heaviest_item(),
+         Some((ItemPosition { elf: 4, item: 0 }, 10000))
+      );
+   }
+
+   #[test]
+   fn rebalances_the_example_evenly() {
+      let inventory = Inventory::parse(EXAMPLE).unwrap();
+      let rebalanced = inventory.rebalance();
+
+      let totals = rebalanced.elves.iter().map(Elf::total).collect::<Vec<_>>();
+      assert_eq!(totals, [11000; 5]);
+
+      let sorted_items = |inventory: &Inventory| {
+         let mut items = inventory
+            .items()
+            .map(|(_, calories)| calories)
+            .collect::<Vec<_>>();
+         items.sort();
+         items
+      };
+      assert_eq!(sorted_items(&rebalanced), sorted_items(&inventory));
+   }
+
+   #[test]
+   fn moves_as_few_items_as_possible_under_the_limit() {
+      let inventory = Inventory::parse(EXAMPLE).unwrap();
+
+      let plan = inventory.moves_to_cap(24000).unwrap();
+      assert!(plan.moves.is_empty() && plan.is_minimal());
+
+      let plan = inventory.moves_to_cap(15000).unwrap();
+      assert_eq!(
+         plan.moves,
+         [Move {
+            item: ItemPosition { elf: 3, item: 2 },
+            calories: 9000,
+            to: 0
+         }]
+      );
+      assert!(plan.is_minimal());
+
+      // Every elf has to end up with exactly 11000, which needs a repack.
+      let plan = inventory.moves_to_cap(11000).unwrap();
+      assert_eq!(plan.lower_bound, 2);
+      let capped = inventory.apply(&plan.moves);
+      assert!(capped.elves.iter().all(|elf| elf.total() <= 11000));
+
+      assert!(inventory.moves_to_cap(9999).is_err());
+      assert!(inventory.moves_to_cap(10999).is_err());
+   }
+
+   proptest! {
+      #[test]
+      fn keeps_the_same_elves_as_sorting(food_bags in prop::collection::vec(0..100u32, 0..50), k in 0..10usize) {
+         let mut top = TopElves::new(k);
+         top.extend(food_bags.iter().enumerate().map(|(index, &calories)| ElfTotal { calories, index }));
+
+         let mut reference = food_bags
+            .iter()
+            .enumerate()
+            .map(|(index, &calories)| ElfTotal { calories, index })
+            .collect::<Vec<_>>();
+         reference.sort_by_key(|elf| (Reverse(elf.calories), elf.index));
+         reference.truncate(k);
+
+         prop_assert_eq!(top.into_sorted_vec(), reference);
+      }
+
+      #[test]
+      fn capping_plans_respect_the_limit(
+         elves in prop::collection::vec(prop::collection::vec(1..100u32, 1..6), 1..8),
+         limit in 50..300u64,
+      ) {
+         let inventory = Inventory {
+            elves: elves.into_iter().map(|items| Elf { items }).collect(),
+         };
+
+         if let Ok(plan) = inventory.moves_to_cap(limit) {
+            prop_assert!(plan.moves.len() >= plan.lower_bound);
+
+            let capped = inventory.apply(&plan.moves);
+            prop_assert!(capped.elves.iter().all(|elf| elf.total() <= limit));
+            prop_assert_eq!(
+               capped.elves.iter().map(Elf::total).sum::<u64>(),
+               inventory.elves.iter().map(Elf::total).sum::<u64>()
+            );
+         }
+      }
+   }
 }
@@ -1,121 +1,668 @@
-enum Part {
-   One,
-   Two,
-}
+//! Day 2: Rock Paper Scissors
+
+use std::{
+   fmt::{self, Display},
+   path::Path,
+   sync::LazyLock,
+};
+
+use crate::{error::ParseError, input::read_input, runner::format_parse_error, solution::Solution};
 
-#[derive(Copy, Clone, PartialEq)]
-enum Shape {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Shape {
    Rock = 1,
    Paper = 2,
    Scissors = 3,
 }
 
-#[derive(Copy, Clone)]
-enum Outcome {
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
    Loss = 0,
    Draw = 3,
    Win = 6,
 }
 
-type Score = u32;
+pub type Score = u32;
 
-#[derive(Copy, Clone)]
-struct Game(Shape, Shape);
+impl Shape {
+   pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
 
-#[derive(Copy, Clone)]
-struct Strategy(Shape, Outcome);
+   /// The shape's index in [`Rules::rock_paper_scissors`].
+   pub fn index(self) -> usize {
+      match self {
+         Shape::Rock => 0,
+         Shape::Paper => 1,
+         Shape::Scissors => 2,
+      }
+   }
+}
 
-impl Into<Game> for Strategy {
-   fn into(self) -> Game {
-      use Outcome::*;
-      use Shape::*;
+impl Outcome {
+   pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
 
-      let player_shape = match self {
-         Strategy(Rock, Loss) => Scissors,
-         Strategy(Rock, Draw) => Rock,
-         Strategy(Rock, Win) => Paper,
+   /// The outcome's index in [`Outcome::ALL`].
+   pub fn index(self) -> usize {
+      match self {
+         Outcome::Loss => 0,
+         Outcome::Draw => 1,
+         Outcome::Win => 2,
+      }
+   }
 
-         Strategy(Paper, Loss) => Rock,
-         Strategy(Paper, Draw) => Paper,
-         Strategy(Paper, Win) => Scissors,
+   /// The outcome for the other player.
+   pub fn reverse(self) -> Outcome {
+      match self {
+         Outcome::Loss => Outcome::Win,
+         Outcome::Draw => Outcome::Draw,
+         Outcome::Win => Outcome::Loss,
+      }
+   }
+}
 
-         Strategy(Scissors, Loss) => Paper,
-         Strategy(Scissors, Draw) => Scissors,
-         Strategy(Scissors, Win) => Rock,
+/// A game like rock paper scissors: its shapes, which shape beats which, and
+/// how a round is scored. Shapes are referred to by their index in
+/// [`Rules::shapes`].
+///
+/// The outcome of every pair of shapes, and which shapes reach each outcome
+/// against each shape, are worked out once from the beats relation.
+#[derive(Debug, Clone)]
+pub struct Rules {
+   shapes: Vec<String>,
+   shape_scores: Vec<Score>,
+   /// The scores for a loss, a draw and a win.
+   outcome_scores: [Score; 3],
+   /// `outcomes[opponent][player]` is the outcome for the player.
+   outcomes: Vec<Vec<Outcome>>,
+   /// `responses[opponent][outcome]` lists the shapes that reach the outcome.
+   responses: Vec<[Vec<usize>; 3]>,
+}
+
+impl Rules {
+   /// Creates the rules from the shapes and every pair `(winner, loser)`.
+   /// Every pair of different shapes must appear exactly once, one way round.
+   ///
+   /// Each shape scores one more than the previous one, starting at 1, and
+   /// a loss, draw or win scores 0, 3 or 6.
+   pub fn new(shapes: &[&str], beats: &[(&str, &str)]) -> Result<Self, String> {
+      let index = |name: &str| {
+         shapes
+            .iter()
+            .position(|&shape| shape == name)
+            .ok_or_else(|| format!("`{}` is not one of the shapes", name))
       };
 
-      Game(self.0, player_shape)
+      if let Some(repeated) =
+         (0..shapes.len()).find(|&shape| shapes[..shape].contains(&shapes[shape]))
+      {
+         return Err(format!("`{}` is listed more than once", shapes[repeated]));
+      }
+
+      let mut outcomes = vec![vec![Outcome::Draw; shapes.len()]; shapes.len()];
+      for &(winner, loser) in beats {
+         let (winner_index, loser_index) = (index(winner)?, index(loser)?);
+
+         if winner_index == loser_index {
+            return Err(format!("`{}` cannot beat itself", winner));
+         }
+
+         if outcomes[loser_index][winner_index] != Outcome::Draw {
+            return Err(format!(
+               "the rules say who wins between `{}` and `{}` more than once",
+               winner, loser
+            ));
+         }
+
+         outcomes[loser_index][winner_index] = Outcome::Win;
+         outcomes[winner_index][loser_index] = Outcome::Loss;
+      }
+
+      for (first, second) in
+         (0..shapes.len()).flat_map(|a| (a + 1..shapes.len()).map(move |b| (a, b)))
+      {
+         if outcomes[first][second] == Outcome::Draw {
+            return Err(format!(
+               "the rules do not say who wins between `{}` and `{}`",
+               shapes[first], shapes[second]
+            ));
+         }
+      }
+
+      let responses = outcomes
+         .iter()
+         .map(|row| {
+            Outcome::ALL.map(|outcome| {
+               (0..row.len())
+                  .filter(|&player| row[player] == outcome)
+                  .collect()
+            })
+         })
+         .collect();
+
+      Ok(Rules {
+         shapes: shapes.iter().map(|&shape| shape.to_owned()).collect(),
+         shape_scores: (1..=shapes.len() as Score).collect(),
+         outcome_scores: Outcome::ALL.map(|outcome| outcome as Score),
+         outcomes,
+         responses,
+      })
+   }
+
+   /// Creates the rules for an odd number of shapes arranged in a circle,
+   /// where each shape beats the shapes an odd number of places before it.
+   /// With three shapes, this is rock paper scissors.
+   pub fn cyclic(shapes: &[&str]) -> Result<Self, String> {
+      if shapes.len() % 2 == 0 {
+         return Err(format!(
+            "a cyclic game needs an odd number of shapes, not {}",
+            shapes.len()
+         ));
+      }
+
+      let beats = (0..shapes.len())
+         .flat_map(|winner| {
+            (1..shapes.len()).step_by(2).map(move |offset| {
+               let loser = (winner + shapes.len() - offset) % shapes.len();
+               (shapes[winner], shapes[loser])
+            })
+         })
+         .collect::<Vec<_>>();
+
+      Self::new(shapes, &beats)
+   }
+
+   pub fn rock_paper_scissors() -> Self {
+      Self::cyclic(&["Rock", "Paper", "Scissors"]).expect("the rules are valid")
+   }
+
+   pub fn rock_paper_scissors_lizard_spock() -> Self {
+      Self::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).expect("the rules are valid")
+   }
+
+   /// Replaces the score of each shape, in the order of [`Rules::shapes`].
+   pub fn with_shape_scores(mut self, scores: &[Score]) -> Result<Self, String> {
+      if scores.len() != self.shapes.len() {
+         return Err(format!(
+            "expected {} shape scores, got {}",
+            self.shapes.len(),
+            scores.len()
+         ));
+      }
+
+      self.shape_scores = scores.to_vec();
+      Ok(self)
+   }
+
+   pub fn with_outcome_scores(mut self, loss: Score, draw: Score, win: Score) -> Self {
+      self.outcome_scores = [loss, draw, win];
+      self
+   }
+
+   pub fn shapes(&self) -> &[String] {
+      &self.shapes
+   }
+
+   pub fn shape(&self, name: &str) -> Option<usize> {
+      self.shapes.iter().position(|shape| shape == name)
+   }
+
+   /// The outcome of a round for the player.
+   pub fn outcome(&self, opponent: usize, player: usize) -> Outcome {
+      self.outcomes[opponent][player]
+   }
+
+   /// Every shape that reaches the outcome against the opponent's shape.
+   pub fn responses(&self, opponent: usize, outcome: Outcome) -> &[usize] {
+      &self.responses[opponent][outcome.index()]
+   }
+
+   /// The highest scoring shape that reaches the outcome against the
+   /// opponent's shape, or the first of them if several score the same.
+   pub fn response(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+      self
+         .responses(opponent, outcome)
+         .iter()
+         .copied()
+         .rev()
+         .max_by_key(|&shape| self.shape_scores[shape])
+   }
+
+   /// The player's score for a round.
+   pub fn score(&self, opponent: usize, player: usize) -> Score {
+      let outcome = self.outcome(opponent, player);
+      self.shape_scores[player] + self.outcome_scores[outcome.index()]
    }
 }
 
-fn parse_shape(letter: &'static str) -> Shape {
+/// The rules of the puzzle.
+static ROCK_PAPER_SCISSORS: LazyLock<Rules> = LazyLock::new(Rules::rock_paper_scissors);
+
+/// A round of the game: the opponent's shape, then the player's shape.
+#[derive(Debug, Copy, Clone)]
+pub struct Game(pub Shape, pub Shape);
+
+/// The opponent's shape, and the outcome the player should aim for.
+#[derive(Debug, Copy, Clone)]
+pub struct Strategy(pub Shape, pub Outcome);
+
+/// A line of the strategy guide. The second column is read as the player's
+/// shape in part one, and as the desired outcome in part two.
+#[derive(Debug, Copy, Clone)]
+pub struct Round {
+   pub opponent: Shape,
+   pub player: Shape,
+   pub outcome: Outcome,
+}
+
+impl From<Strategy> for Game {
+   fn from(Strategy(opponent, outcome): Strategy) -> Game {
+      let player = ROCK_PAPER_SCISSORS
+         .response(opponent.index(), outcome)
+         .expect("every outcome can be reached");
+
+      Game(opponent, Shape::ALL[player])
+   }
+}
+
+fn parse_shape(letter: &str) -> Option<Shape> {
    use Shape::*;
 
    match letter {
-      "A" | "X" => Rock,
-      "B" | "Y" => Paper,
-      "C" | "Z" => Scissors,
+      "A" | "X" => Some(Rock),
+      "B" | "Y" => Some(Paper),
+      "C" | "Z" => Some(Scissors),
 
-      _ => unreachable!(),
+      _ => None,
    }
 }
 
-fn parse_outcome(letter: &'static str) -> Outcome {
+fn parse_outcome(letter: &str) -> Option<Outcome> {
    use Outcome::*;
 
    match letter {
-      "X" => Loss,
-      "Y" => Draw,
-      "Z" => Win,
+      "X" => Some(Loss),
+      "Y" => Some(Draw),
+      "Z" => Some(Win),
 
-      _ => unreachable!(),
+      _ => None,
    }
 }
 
-fn parse_input(part: Part) -> Vec<Game> {
-   include_str!("../input/day02.txt")
+fn parse_round(line_number: usize, line: &str) -> Result<Round, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day02::DAY, line_number, line, text, message);
+
+   let (opponent, player) = line
+      .split_once(' ')
+      .ok_or_else(|| error(line, "expected two letters separated by a space"))?;
+
+   let opponent = match opponent {
+      "A" | "B" | "C" => parse_shape(opponent),
+      _ => None,
+   }
+   .ok_or_else(|| error(opponent, "expected the opponent's shape (A, B or C)"))?;
+
+   let (player, outcome) = parse_shape(player)
+      .zip(parse_outcome(player))
+      .ok_or_else(|| error(player, "expected X, Y or Z"))?;
+
+   Ok(Round {
+      opponent,
+      player,
+      outcome,
+   })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Round>, ParseError> {
+   input
       .lines()
-      .map(|line| {
-         let tokens: Vec<&'static str> = line.split(" ").collect();
-         let opponent = parse_shape(tokens[0]);
-         let player = parse_shape(tokens[1]);
-         let outcome = parse_outcome(tokens[1]);
-
-         match part {
-            Part::One => Game(opponent, player),
-            Part::Two => Strategy(opponent, outcome).into(),
-         }
-      })
+      .enumerate()
+      .map(|(index, line)| parse_round(index + 1, line))
       .collect()
 }
 
-fn get_outcome(game: Game) -> Outcome {
-   use Outcome::*;
-   use Shape::*;
+pub fn get_outcome(Game(opponent, player): Game) -> Outcome {
+   ROCK_PAPER_SCISSORS.outcome(opponent.index(), player.index())
+}
 
-   match game {
-      Game(Rock, Rock) => Draw,
-      Game(Rock, Paper) => Win,
-      Game(Rock, Scissors) => Loss,
+pub fn evaluate(Game(opponent, player): Game) -> Score {
+   ROCK_PAPER_SCISSORS.score(opponent.index(), player.index())
+}
 
-      Game(Paper, Rock) => Loss,
-      Game(Paper, Paper) => Draw,
-      Game(Paper, Scissors) => Win,
+/// A way to read the second column of the strategy guide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+   /// X, Y and Z are the shapes to play, as in part one.
+   Shapes([Shape; 3]),
+   /// X, Y and Z are the outcomes to aim for, as in part two.
+   Outcomes([Outcome; 3]),
+}
+
+/// Every ordering of three things.
+fn permutations<T: Copy>([a, b, c]: [T; 3]) -> [[T; 3]; 6] {
+   [
+      [a, b, c],
+      [a, c, b],
+      [b, a, c],
+      [b, c, a],
+      [c, a, b],
+      [c, b, a],
+   ]
+}
 
-      Game(Scissors, Rock) => Win,
-      Game(Scissors, Paper) => Loss,
-      Game(Scissors, Scissors) => Draw,
+impl Interpretation {
+   /// The 6 ways to map X, Y and Z to shapes, then the 6 ways to map them
+   /// to outcomes.
+   pub fn all() -> Vec<Interpretation> {
+      let shapes = permutations(Shape::ALL).map(Interpretation::Shapes);
+      let outcomes = permutations(Outcome::ALL).map(Interpretation::Outcomes);
+      shapes.into_iter().chain(outcomes).collect()
+   }
+
+   /// The player's shape when the opponent plays `opponent` and the guide
+   /// says `letter` (0 for X, 1 for Y and 2 for Z).
+   fn player(self, opponent: Shape, letter: usize) -> Shape {
+      match self {
+         Interpretation::Shapes(shapes) => shapes[letter],
+         Interpretation::Outcomes(outcomes) => {
+            let Game(_, player) = Strategy(opponent, outcomes[letter]).into();
+            player
+         }
+      }
    }
 }
 
-fn evaluate(game: Game) -> Score {
-   (game.1 as Score) + (get_outcome(game) as Score)
+impl Display for Interpretation {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      let (meanings, kind) = match self {
+         Interpretation::Shapes(shapes) => (shapes.map(|shape| format!("{:?}", shape)), "shapes"),
+         Interpretation::Outcomes(outcomes) => {
+            (outcomes.map(|outcome| format!("{:?}", outcome)), "outcomes")
+         }
+      };
+
+      write!(
+         f,
+         "X={}, Y={}, Z={} ({})",
+         meanings[0], meanings[1], meanings[2], kind
+      )
+   }
 }
 
-pub fn solve_part_one() -> Score {
-   parse_input(Part::One).into_iter().map(evaluate).sum()
+/// The total score of the strategy guide under every interpretation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoding {
+   /// Each interpretation and its score, in the order of
+   /// [`Interpretation::all`].
+   pub scores: Vec<(Interpretation, Score)>,
 }
 
-pub fn solve_part_two() -> Score {
-   parse_input(Part::Two).into_iter().map(evaluate).sum()
+impl Decoding {
+   /// Decodes the strategy guide in a file, or in stdin if the path is `-`.
+   pub fn from_path(path: &Path) -> Result<Self, String> {
+      let input = read_input(path)?;
+      let rounds =
+         Day02::parse(&input).map_err(|error| format_parse_error(&error, path, &input))?;
+      Ok(Decoding::new(&rounds))
+   }
+
+   pub fn new(rounds: &[Round]) -> Self {
+      // The guide's letter is kept as the shape it means in part one.
+      let mut counts = [[0 as Score; 3]; 3];
+      for round in rounds {
+         counts[round.opponent.index()][round.player.index()] += 1;
+      }
+
+      let scores = Interpretation::all()
+         .into_iter()
+         .map(|interpretation| {
+            let score = Shape::ALL
+               .iter()
+               .flat_map(|&opponent| (0..3).map(move |letter| (opponent, letter)))
+               .map(|(opponent, letter)| {
+                  let player = interpretation.player(opponent, letter);
+                  counts[opponent.index()][letter] * evaluate(Game(opponent, player))
+               })
+               .sum();
+
+            (interpretation, score)
+         })
+         .collect();
+
+      Decoding { scores }
+   }
+
+   /// The interpretations with the highest score; several if they tie.
+   pub fn best(&self) -> Vec<(Interpretation, Score)> {
+      let best = self.scores.iter().map(|&(_, score)| score).max();
+      self.with_score(best)
+   }
+
+   /// The interpretations with the lowest score; several if they tie.
+   pub fn worst(&self) -> Vec<(Interpretation, Score)> {
+      let worst = self.scores.iter().map(|&(_, score)| score).min();
+      self.with_score(worst)
+   }
+
+   /// The interpretations that give the observed total score.
+   pub fn consistent_with(&self, observed: Score) -> Vec<Interpretation> {
+      self
+         .with_score(Some(observed))
+         .into_iter()
+         .map(|(interpretation, _)| interpretation)
+         .collect()
+   }
+
+   /// Every interpretation and its score, the highest scoring first.
+   pub fn ranked(&self) -> Vec<(Interpretation, Score)> {
+      let mut ranked = self.scores.clone();
+      ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+      ranked
+   }
+
+   fn with_score(&self, score: Option<Score>) -> Vec<(Interpretation, Score)> {
+      self
+         .scores
+         .iter()
+         .copied()
+         .filter(|&(_, other)| Some(other) == score)
+         .collect()
+   }
+}
+
+impl Display for Decoding {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      for (interpretation, score) in self.ranked() {
+         writeln!(f, "{:>8}  {}", score, interpretation)?;
+      }
+
+      let list = |scores: Vec<(Interpretation, Score)>| {
+         scores
+            .into_iter()
+            .map(|(interpretation, score)| format!("{} scores {}", interpretation, score))
+            .collect::<Vec<_>>()
+            .join("; ")
+      };
+
+      writeln!(f, "\nBest: {}", list(self.best()))?;
+      writeln!(f, "Worst: {}", list(self.worst()))
+   }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+   const DAY: u8 = 2;
+
+   type Input = Vec<Round>;
+   type PartOne = Score;
+   type PartTwo = Score;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(rounds: &Self::Input) -> Score {
+      rounds
+         .iter()
+         .map(|round| evaluate(Game(round.opponent, round.player)))
+         .sum()
+   }
+
+   fn part_two(rounds: &Self::Input) -> Score {
+      rounds
+         .iter()
+         .map(|round| evaluate(Strategy(round.opponent, round.outcome).into()))
+         .sum()
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   const EXAMPLE: &str = "\
+A Y
+B X
+C Z";
+
+   #[test]
+   fn part_one_example() {
+      let rounds = Day02::parse(EXAMPLE).unwrap();
+      assert_eq!(Day02::part_one(&rounds), 15);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let rounds = Day02::parse(EXAMPLE).unwrap();
+      assert_eq!(Day02::part_two(&rounds), 12);
+   }
+
+   #[test]
+   fn derives_the_rock_paper_scissors_tables() {
+      use Outcome::*;
+      use Shape::*;
+
+      let expected = [
+         (Rock, Rock, Draw),
+         (Rock, Paper, Win),
+         (Rock, Scissors, Loss),
+         (Paper, Rock, Loss),
+         (Paper, Paper, Draw),
+         (Paper, Scissors, Win),
+         (Scissors, Rock, Win),
+         (Scissors, Paper, Loss),
+         (Scissors, Scissors, Draw),
+      ];
+
+      for (opponent, player, outcome) in expected {
+         assert_eq!(get_outcome(Game(opponent, player)), outcome);
+
+         let Game(_, response) = Strategy(opponent, outcome).into();
+         assert_eq!(response, player);
+      }
+   }
+
+   #[test]
+   fn derives_rock_paper_scissors_lizard_spock() {
+      let rules = Rules::rock_paper_scissors_lizard_spock();
+      let shape = |name| rules.shape(name).unwrap();
+
+      for (winner, loser) in [
+         ("Scissors", "Paper"),
+         ("Paper", "Rock"),
+         ("Rock", "Lizard"),
+         ("Lizard", "Spock"),
+         ("Spock", "Scissors"),
+         ("Scissors", "Lizard"),
+         ("Lizard", "Paper"),
+         ("Paper", "Spock"),
+         ("Spock", "Rock"),
+         ("Rock", "Scissors"),
+      ] {
+         assert_eq!(rules.outcome(shape(loser), shape(winner)), Outcome::Win);
+         assert_eq!(rules.outcome(shape(winner), shape(loser)), Outcome::Loss);
+      }
+
+      // Every shape is beaten by two others, and every response works.
+      for opponent in 0..rules.shapes().len() {
+         assert_eq!(rules.responses(opponent, Outcome::Win).len(), 2);
+
+         for outcome in Outcome::ALL {
+            for &player in rules.responses(opponent, outcome) {
+               assert_eq!(rules.outcome(opponent, player), outcome);
+               assert_eq!(rules.outcome(player, opponent), outcome.reverse());
+            }
+         }
+      }
+   }
+
+   #[test]
+   fn scores_with_configurable_weights() {
+      let rules = Rules::rock_paper_scissors()
+         .with_shape_scores(&[10, 20, 30])
+         .unwrap()
+         .with_outcome_scores(0, 1, 2);
+
+      assert_eq!(rules.score(0, 1), 22);
+      assert_eq!(rules.score(2, 2), 31);
+      assert!(Rules::rock_paper_scissors()
+         .with_shape_scores(&[1, 2])
+         .is_err());
+   }
+
+   #[test]
+   fn decodes_the_guide_under_every_interpretation() {
+      use Outcome::*;
+      use Shape::*;
+
+      let rounds = Day02::parse(EXAMPLE).unwrap();
+      let decoding = Decoding::new(&rounds);
+      assert_eq!(decoding.scores.len(), 12);
+
+      let part_one = Interpretation::Shapes([Rock, Paper, Scissors]);
+      let part_two = Interpretation::Outcomes([Loss, Draw, Win]);
+      assert!(decoding.scores.contains(&(part_one, 15)));
+      assert!(decoding.scores.contains(&(part_two, 12)));
+
+      // Reading X, Y and Z as Scissors, Paper and Rock wins every round,
+      // and reading them as Rock, Scissors and Paper loses every round.
+      assert_eq!(
+         decoding.best(),
+         [(Interpretation::Shapes([Scissors, Paper, Rock]), 24)]
+      );
+      assert_eq!(
+         decoding.worst(),
+         [(Interpretation::Shapes([Rock, Scissors, Paper]), 6)]
+      );
+
+      let ranked = decoding.ranked();
+      assert_eq!(ranked[0], decoding.best()[0]);
+      assert_eq!(ranked[11], decoding.worst()[0]);
+      assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+      assert_eq!(decoding.consistent_with(12), [part_two]);
+      assert_eq!(decoding.consistent_with(15).len(), 8);
+      assert!(decoding.consistent_with(15).contains(&part_one));
+      assert!(decoding.consistent_with(1000).is_empty());
+   }
+
+   #[test]
+   fn rejects_incomplete_or_contradictory_rules() {
+      let shapes = ["Rock", "Paper", "Scissors"];
+
+      assert!(Rules::new(&shapes, &[("Paper", "Rock"), ("Rock", "Scissors")]).is_err());
+      assert!(Rules::new(
+         &shapes,
+         &[
+            ("Paper", "Rock"),
+            ("Rock", "Paper"),
+            ("Scissors", "Paper"),
+            ("Rock", "Scissors")
+         ]
+      )
+      .is_err());
+      assert!(Rules::new(&shapes, &[("Rock", "Rock")]).is_err());
+      assert!(Rules::cyclic(&["Rock", "Paper"]).is_err());
+      assert!(Rules::cyclic(&["Rock", "Paper", "Rock"]).is_err());
+   }
 }
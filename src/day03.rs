@@ -1,29 +1,51 @@
+//! Day 3: Rucksack Reorganization
+
 use std::collections::HashSet;
 
-type Item = char;
+use crate::{error::ParseError, solution::Solution};
+
+pub type Item = char;
 
-type Priority = u32;
+pub type Priority = u32;
 
-struct Rucksack(HashSet<Item>, HashSet<Item>);
+/// The items in each of a rucksack's two compartments.
+#[derive(Debug)]
+pub struct Rucksack(pub HashSet<Item>, pub HashSet<Item>);
 
 impl Rucksack {
-   fn all_items(&self) -> HashSet<Item> {
+   pub fn all_items(&self) -> HashSet<Item> {
       self.0.union(&self.1).copied().collect()
    }
 }
 
-fn parse_input() -> Vec<Rucksack> {
-   include_str!("../input/day03.txt")
+fn parse_rucksack(line_number: usize, line: &str) -> Result<Rucksack, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day03::DAY, line_number, line, text, message);
+
+   if let Some(index) = line.find(|item: Item| !item.is_ascii_alphabetic()) {
+      let item = &line[index..index + line[index..].chars().next().unwrap().len_utf8()];
+      return Err(error(item, "expected an item (a-z or A-Z)"));
+   }
+
+   // Every item is a single ASCII letter, so byte and item counts agree.
+   let rucksack_size = line.len();
+   if rucksack_size % 2 == 1 {
+      return Err(error(line, "expected an even number of items"));
+   }
+
+   let (first, second) = line.split_at(rucksack_size / 2);
+   Ok(Rucksack(first.chars().collect(), second.chars().collect()))
+}
+
+fn parse_input(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+   input
       .lines()
-      .map(|line| {
-         let rucksack_size = line.chars().count();
-         let (first, second) = line.split_at(rucksack_size / 2);
-         Rucksack(first.chars().collect(), second.chars().collect())
-      })
+      .enumerate()
+      .map(|(index, line)| parse_rucksack(index + 1, line))
       .collect()
 }
 
-fn get_priority(item: Item) -> Priority {
+pub fn get_priority(item: Item) -> Priority {
    match item {
       'a'..='z' => u32::from(item) - 97 + 1,
       'A'..='Z' => u32::from(item) - 65 + 27,
@@ -31,11 +53,11 @@ fn get_priority(item: Item) -> Priority {
    }
 }
 
-fn find_common_items(rucksack: Rucksack) -> HashSet<Item> {
+pub fn find_common_items(rucksack: &Rucksack) -> HashSet<Item> {
    rucksack.0.intersection(&rucksack.1).copied().collect()
 }
 
-fn find_group_badges(rucksacks: &[Rucksack]) -> HashSet<Item> {
+pub fn find_group_badges(rucksacks: &[Rucksack]) -> HashSet<Item> {
    if rucksacks.is_empty() || rucksacks.len() == 1 {
       return HashSet::new();
    }
@@ -48,19 +70,57 @@ fn find_group_badges(rucksacks: &[Rucksack]) -> HashSet<Item> {
       })
 }
 
-pub fn solve_part_one() -> Priority {
-   parse_input()
-      .into_iter()
-      .flat_map(|rucksack| find_common_items(rucksack))
-      .map(get_priority)
-      .sum()
+pub struct Day03;
+
+impl Solution for Day03 {
+   const DAY: u8 = 3;
+
+   type Input = Vec<Rucksack>;
+   type PartOne = Priority;
+   type PartTwo = Priority;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(rucksacks: &Self::Input) -> Priority {
+      rucksacks
+         .iter()
+         .flat_map(find_common_items)
+         .map(get_priority)
+         .sum()
+   }
+
+   fn part_two(rucksacks: &Self::Input) -> Priority {
+      rucksacks
+         .chunks(3)
+         .flat_map(find_group_badges)
+         .map(get_priority)
+         .sum()
+   }
 }
 
-pub fn solve_part_two() -> Priority {
-   parse_input()
-      .chunks(3)
-      .into_iter()
-      .flat_map(|rucksacks| find_group_badges(rucksacks))
-      .map(get_priority)
-      .sum()
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   const EXAMPLE: &str = "\
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
+
+   #[test]
+   fn part_one_example() {
+      let rucksacks = Day03::parse(EXAMPLE).unwrap();
+      assert_eq!(Day03::part_one(&rucksacks), 157);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let rucksacks = Day03::parse(EXAMPLE).unwrap();
+      assert_eq!(Day03::part_two(&rucksacks), 70);
+   }
 }
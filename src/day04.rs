@@ -1,15 +1,20 @@
-#[derive(Debug)]
-struct Assignment {
-   start: u8,
-   end: u8,
+//! Day 4: Camp Cleanup
+
+use crate::{error::ParseError, solution::Solution};
+
+/// An inclusive range of section IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assignment {
+   pub start: u8,
+   pub end: u8,
 }
 
 impl Assignment {
-   fn fully_contains(self: &Self, other: &Assignment) -> bool {
+   pub fn fully_contains(&self, other: &Assignment) -> bool {
       self.start <= other.start && self.end >= other.end
    }
 
-   fn overlaps_with(self: &Self, other: &Assignment) -> bool {
+   pub fn overlaps_with(&self, other: &Assignment) -> bool {
       (self.start >= other.start && self.start <= other.end)
          || (self.end >= other.start && self.end <= other.end)
          || (other.start >= self.start && other.start <= self.end)
@@ -17,47 +22,156 @@ impl Assignment {
    }
 }
 
-#[derive(Debug)]
-struct AssignmentPair {
-   first: Assignment,
-   second: Assignment,
+#[derive(Debug, Clone, Copy)]
+pub struct AssignmentPair {
+   pub first: Assignment,
+   pub second: Assignment,
 }
 
-fn parse_assignment(assignment: &'static str) -> Assignment {
-   assignment
-      .split_once("-")
-      .and_then(|(start, end)| {
-         Some(Assignment {
-            start: start.parse().unwrap(),
-            end: end.parse().unwrap(),
-         })
-      })
-      .unwrap()
+fn parse_assignment(
+   line_number: usize,
+   line: &str,
+   assignment: &str,
+) -> Result<Assignment, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day04::DAY, line_number, line, text, message);
+
+   let (start, end) = assignment
+      .split_once('-')
+      .ok_or_else(|| error(assignment, "expected a section range such as `2-4`"))?;
+
+   let parse_section = |section: &str| {
+      section
+         .parse()
+         .map_err(|_| error(section, "expected a section number"))
+   };
+
+   let assignment = Assignment {
+      start: parse_section(start)?,
+      end: parse_section(end)?,
+   };
+
+   if assignment.start > assignment.end {
+      return Err(error(end, "section range ends before it starts"));
+   }
+
+   Ok(assignment)
 }
 
-fn parse_input() -> Vec<AssignmentPair> {
-   include_str!("../input/day04.txt")
+fn parse_input(input: &str) -> Result<Vec<AssignmentPair>, ParseError> {
+   input
       .lines()
-      .map(|line| {
-         let (first_elf, second_elf) = line.split_once(",").unwrap();
-         AssignmentPair {
-            first: parse_assignment(first_elf),
-            second: parse_assignment(second_elf),
-         }
+      .enumerate()
+      .map(|(index, line)| {
+         let line_number = index + 1;
+         let (first_elf, second_elf) = line.split_once(',').ok_or_else(|| {
+            ParseError::new(
+               Day04::DAY,
+               line_number,
+               line,
+               line,
+               "expected two comma-separated section ranges",
+            )
+         })?;
+
+         Ok(AssignmentPair {
+            first: parse_assignment(line_number, line, first_elf)?,
+            second: parse_assignment(line_number, line, second_elf)?,
+         })
       })
       .collect()
 }
 
-pub fn solve_part_one() -> usize {
-   parse_input()
-      .iter()
-      .filter(|p| p.first.fully_contains(&p.second) || p.second.fully_contains(&p.first))
-      .count()
+pub struct Day04;
+
+impl Solution for Day04 {
+   const DAY: u8 = 4;
+
+   type Input = Vec<AssignmentPair>;
+   type PartOne = usize;
+   type PartTwo = usize;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(pairs: &Self::Input) -> usize {
+      pairs
+         .iter()
+         .filter(|p| p.first.fully_contains(&p.second) || p.second.fully_contains(&p.first))
+         .count()
+   }
+
+   fn part_two(pairs: &Self::Input) -> usize {
+      pairs
+         .iter()
+         .filter(|p| p.first.overlaps_with(&p.second))
+         .count()
+   }
 }
 
-pub fn solve_part_two() -> usize {
-   parse_input()
-      .iter()
-      .filter(|p| p.first.overlaps_with(&p.second))
-      .count()
+#[cfg(test)]
+mod tests {
+   use std::collections::HashSet;
+
+   use proptest::prelude::*;
+
+   use super::*;
+
+   const EXAMPLE: &str = "\
+2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+   #[test]
+   fn part_one_example() {
+      let pairs = Day04::parse(EXAMPLE).unwrap();
+      assert_eq!(Day04::part_one(&pairs), 2);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let pairs = Day04::parse(EXAMPLE).unwrap();
+      assert_eq!(Day04::part_two(&pairs), 4);
+   }
+
+   fn assignment() -> impl Strategy<Value = Assignment> {
+      (1..100u8, 1..100u8).prop_map(|(a, b)| Assignment {
+         start: a.min(b),
+         end: a.max(b),
+      })
+   }
+
+   /// Naive reference: the sections each assignment covers, as sets.
+   fn sections(assignment: &Assignment) -> HashSet<u8> {
+      (assignment.start..=assignment.end).collect()
+   }
+
+   proptest! {
+      #[test]
+      fn overlaps_with_is_symmetric(first in assignment(), second in assignment()) {
+         prop_assert_eq!(first.overlaps_with(&second), second.overlaps_with(&first));
+      }
+
+      #[test]
+      fn overlaps_with_matches_shared_sections(first in assignment(), second in assignment()) {
+         let shared = sections(&first).intersection(&sections(&second)).count();
+         prop_assert_eq!(first.overlaps_with(&second), shared > 0);
+      }
+
+      #[test]
+      fn fully_contains_matches_subsets(first in assignment(), second in assignment()) {
+         prop_assert_eq!(
+            first.fully_contains(&second),
+            sections(&second).is_subset(&sections(&first))
+         );
+
+         if first.fully_contains(&second) {
+            prop_assert!(first.overlaps_with(&second));
+         }
+      }
+   }
 }
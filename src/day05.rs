@@ -1,37 +1,80 @@
-type Crate = char;
+//! Day 5: Supply Stacks
 
-type Stack = Vec<Crate>;
+use crate::{error::ParseError, solution::Solution};
 
-#[derive(Copy, Clone)]
-struct Move {
-   count: usize,
-   from: usize,
-   to: usize,
+pub type Crate = char;
+
+/// A stack of crates, from the bottom up.
+pub type Stack = Vec<Crate>;
+
+/// Moves `count` crates between two stacks, which are indexed from 0.
+#[derive(Debug, Copy, Clone)]
+pub struct Move {
+   pub count: usize,
+   pub from: usize,
+   pub to: usize,
+}
+
+fn parse_move(line_number: usize, line: &str, stack_count: usize) -> Result<Move, ParseError> {
+   let error =
+      |text: &str, message: String| ParseError::new(Day05::DAY, line_number, line, text, message);
+
+   let tokens: Vec<_> = line.split(' ').collect();
+   if tokens.len() != 6 || tokens[0] != "move" || tokens[2] != "from" || tokens[4] != "to" {
+      return Err(error(
+         line,
+         "expected a move such as `move 1 from 2 to 3`".to_owned(),
+      ));
+   }
+
+   let parse_stack = |token: &str| match token.parse::<usize>() {
+      Ok(stack) if (1..=stack_count).contains(&stack) => Ok(stack - 1),
+      _ => Err(error(
+         token,
+         format!("expected a stack number between 1 and {}", stack_count),
+      )),
+   };
+
+   Ok(Move {
+      count: tokens[1]
+         .parse()
+         .map_err(|_| error(tokens[1], "expected a crate count".to_owned()))?,
+      from: parse_stack(tokens[3])?,
+      to: parse_stack(tokens[5])?,
+   })
 }
 
-fn parse_input() -> (Vec<Stack>, Vec<Move>) {
-   let mut input_lines = include_str!("../input/day05.txt")
-      .lines()
-      .collect::<Vec<_>>()
-      .into_iter();
-
-   // Consume until we hit the x-axis.
-   let diagram_text: Vec<&str> = input_lines
-      .by_ref()
-      .take_while(|line| !line.starts_with(" 1"))
-      .collect();
-
-   // Count the number of stacks depicted in the diagram.
-   // Each crate takes up 3 characters (i.e. "[N]"), and
-   // they are delimited by spaces.
-   let stack_count = diagram_text[0].len() / 4 + 1;
+fn parse_input(input: &str) -> Result<(Vec<Stack>, Vec<Move>), ParseError> {
+   let input_lines: Vec<&str> = input.lines().collect();
+
+   // The diagram ends at the x-axis, which numbers each stack.
+   let axis_index = input_lines
+      .iter()
+      .position(|line| line.starts_with(" 1"))
+      .ok_or_else(|| {
+         let last_line = input_lines.last().copied().unwrap_or_default();
+         ParseError::new(
+            Day05::DAY,
+            input_lines.len().max(1),
+            last_line,
+            &last_line[last_line.len()..],
+            "expected a line numbering the stacks below the crate diagram",
+         )
+      })?;
+
+   let diagram_text = &input_lines[..axis_index];
+   let axis = input_lines[axis_index]
+      .split_whitespace()
+      .collect::<Vec<_>>();
+   let stack_count = axis.len();
    let mut stacks: Vec<Stack> = vec![vec![]; stack_count];
 
-   // Parse the crates and add them to the stacks.
-   for line in &diagram_text {
-      for stack_index in 0..stack_count {
+   // Parse the crates and add them to the stacks. Each crate takes up
+   // 3 characters (i.e. "[N]"), and they are delimited by spaces.
+   for line in diagram_text {
+      for (stack_index, stack) in stacks.iter_mut().enumerate() {
          if let Some(value @ 'A'..='Z') = line.chars().nth(stack_index * 4 + 1) {
-            stacks[stack_index].push(value);
+            stack.push(value);
          }
       }
    }
@@ -39,64 +82,209 @@ fn parse_input() -> (Vec<Stack>, Vec<Move>) {
    // Reverse the stacks because we pushed the crates in reverse order.
    stacks.iter_mut().for_each(|stack| stack.reverse());
 
-   // Consume the next empty line in the input.
-   input_lines.next();
+   // The list of moves follows an empty line after the x-axis.
+   if let Some(line) = input_lines.get(axis_index + 1) {
+      if !line.is_empty() {
+         return Err(ParseError::new(
+            Day05::DAY,
+            axis_index + 2,
+            line,
+            line,
+            "expected a blank line between the stacks and the moves",
+         ));
+      }
+   }
 
-   // Parse the list of moves.
-   let moves: Vec<Move> = input_lines
-      .map(|line| {
-         let tokens: Vec<_> = line.split(" ").collect();
+   // Keep track of the height of each stack, so that no move can take more
+   // crates than a stack holds.
+   let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+   let mut moves = vec![];
 
-         Move {
-            count: tokens[1].parse().unwrap(),
-            from: tokens[3].parse::<usize>().unwrap() - 1,
-            to: tokens[5].parse::<usize>().unwrap() - 1,
-         }
-      })
-      .collect();
+   for (index, line) in input_lines.iter().enumerate().skip(axis_index + 2) {
+      let _move = parse_move(index + 1, line, stack_count)?;
 
-   (stacks, moves)
+      if _move.count > heights[_move.from] {
+         return Err(ParseError::new(
+            Day05::DAY,
+            index + 1,
+            line,
+            line.split(' ').nth(1).unwrap_or(line),
+            format!(
+               "stack {} only holds {} crates at this point",
+               _move.from + 1,
+               heights[_move.from]
+            ),
+         ));
+      }
+
+      heights[_move.from] -= _move.count;
+      heights[_move.to] += _move.count;
+      moves.push(_move);
+   }
+
+   // Every stack needs a crate on top once the moves are done.
+   if let Some(empty) = heights.iter().position(|&height| height == 0) {
+      return Err(ParseError::new(
+         Day05::DAY,
+         axis_index + 1,
+         input_lines[axis_index],
+         axis[empty],
+         format!("stack {} is left without any crates", empty + 1),
+      ));
+   }
+
+   Ok((stacks, moves))
 }
 
 // lol, what are the chances that both "move" and "crate"
 // are reserved words in Rust? ¯\_(ツ)_/¯
-fn apply_move(stacks: &mut Vec<Stack>, _move: &Move) {
+/// Moves crates one at a time, like the CrateMover 9000.
+pub fn apply_move(stacks: &mut [Stack], _move: &Move) {
    for _ in 1..=_move.count {
       let _crate = stacks[_move.from].pop().unwrap();
       stacks[_move.to].push(_crate);
    }
 }
 
-fn apply_move_9001(stacks: &mut Vec<Stack>, _move: &Move) {
+/// Moves crates all at once, like the CrateMover 9001.
+pub fn apply_move_9001(stacks: &mut [Stack], _move: &Move) {
    let from = &mut stacks[_move.from];
    let crates = from.split_off(from.len() - _move.count);
    stacks[_move.to].extend(crates);
 }
 
-pub fn solve_part_one() -> String {
-   let (mut stacks, moves) = parse_input();
+pub fn top_crates(stacks: &[Stack]) -> String {
+   String::from_iter(stacks.iter().map(|stack| *stack.last().unwrap()))
+}
+
+/// Draws the stacks the way the puzzle input does, with the x-axis below.
+pub fn draw_stacks(stacks: &[Stack]) -> String {
+   let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+   let mut diagram = String::new();
+
+   for level in (0..height).rev() {
+      let row = stacks
+         .iter()
+         .map(|stack| match stack.get(level) {
+            Some(_crate) => format!("[{}]", _crate),
+            None => "   ".to_owned(),
+         })
+         .collect::<Vec<_>>();
+
+      diagram += &row.join(" ");
+      diagram += "\n";
+   }
+
+   let axis = (1..=stacks.len())
+      .map(|stack| format!(" {} ", stack))
+      .collect::<Vec<_>>();
+
+   diagram += &axis.join(" ");
+   diagram += "\n";
+   diagram
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+   const DAY: u8 = 5;
+
+   type Input = (Vec<Stack>, Vec<Move>);
+   type PartOne = String;
+   type PartTwo = String;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one((stacks, moves): &Self::Input) -> String {
+      let mut stacks = stacks.clone();
+
+      for _move in moves {
+         apply_move(&mut stacks, _move);
+      }
 
-   for _move in moves {
-      apply_move(&mut stacks, &_move);
+      top_crates(&stacks)
    }
 
-   String::from_iter(
-      stacks
-         .into_iter()
-         .map(|stack| stack.last().unwrap().clone()),
-   )
+   fn part_two((stacks, moves): &Self::Input) -> String {
+      let mut stacks = stacks.clone();
+
+      for _move in moves {
+         apply_move_9001(&mut stacks, _move);
+      }
+
+      top_crates(&stacks)
+   }
+
+   fn visualize((stacks, moves): &Self::Input) -> Option<String> {
+      let mut crate_mover_9000 = stacks.clone();
+      let mut crate_mover_9001 = stacks.clone();
+
+      for _move in moves {
+         apply_move(&mut crate_mover_9000, _move);
+         apply_move_9001(&mut crate_mover_9001, _move);
+      }
+
+      Some(format!(
+         "Before the moves:\n\n{}\nAfter the CrateMover 9000:\n\n{}\nAfter the CrateMover 9001:\n\n{}",
+         draw_stacks(stacks),
+         draw_stacks(&crate_mover_9000),
+         draw_stacks(&crate_mover_9001)
+      ))
+   }
 }
 
-pub fn solve_part_two() -> String {
-   let (mut stacks, moves) = parse_input();
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   // Built with `concat!` because the leading spaces of the diagram matter.
+   const EXAMPLE: &str = concat!(
+      "    [D]    \n",
+      "[N] [C]    \n",
+      "[Z] [M] [P]\n",
+      " 1   2   3 \n",
+      "\n",
+      "move 1 from 2 to 1\n",
+      "move 3 from 1 to 3\n",
+      "move 2 from 2 to 1\n",
+      "move 1 from 1 to 2\n",
+   );
 
-   for _move in moves {
-      apply_move_9001(&mut stacks, &_move);
+   #[test]
+   fn part_one_example() {
+      let input = Day05::parse(EXAMPLE).unwrap();
+      assert_eq!(Day05::part_one(&input), "CMZ");
    }
 
-   String::from_iter(
-      stacks
-         .into_iter()
-         .map(|stack| stack.last().unwrap().clone()),
-   )
+   #[test]
+   fn part_two_example() {
+      let input = Day05::parse(EXAMPLE).unwrap();
+      assert_eq!(Day05::part_two(&input), "MCD");
+   }
+
+   #[test]
+   fn rejects_moves_that_take_more_crates_than_a_stack_holds() {
+      let error = Day05::parse("[A]\n 1   2 \n\nmove 3 from 1 to 2").unwrap_err();
+      assert_eq!((error.line, error.column), (4, 6));
+   }
+
+   #[test]
+   fn rejects_moves_without_a_blank_line_before_them() {
+      let error = Day05::parse(" 1   2 \nmove 1 from 1 to 2").unwrap_err();
+      assert_eq!((error.line, error.column), (2, 1));
+   }
+
+   #[test]
+   fn rejects_stacks_left_empty() {
+      let error = Day05::parse("[A]\n 1   2 \n\nmove 1 from 1 to 2").unwrap_err();
+      assert_eq!((error.line, error.column), (2, 2));
+   }
+
+   #[test]
+   fn draws_the_stacks_like_the_input() {
+      let (stacks, _) = Day05::parse(EXAMPLE).unwrap();
+      assert!(EXAMPLE.starts_with(&draw_stacks(&stacks)));
+   }
 }
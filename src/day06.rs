@@ -1,10 +1,31 @@
+//! Day 6: Tuning Trouble
+
 use std::collections::HashSet;
 
-fn parse_input() -> Vec<char> {
-   include_str!("../input/day06.txt").chars().collect()
+use crate::{error::ParseError, solution::Solution};
+
+fn parse_input(input: &str) -> Result<Vec<char>, ParseError> {
+   let buffer = input.trim_end().chars().collect::<Vec<_>>();
+
+   // A start-of-message marker always contains a start-of-packet marker, so
+   // checking for the longer one is enough for both parts to be answered.
+   if find_marker_position(&buffer, 14).is_none() {
+      let line = input.lines().next().unwrap_or_default();
+      return Err(ParseError::new(
+         Day06::DAY,
+         1,
+         line,
+         &line[line.len()..],
+         "expected 14 different characters in a row to mark the start of a message",
+      ));
+   }
+
+   Ok(buffer)
 }
 
-fn find_marker_position(buffer: &Vec<char>, window_size: usize) -> Option<usize> {
+/// Returns the number of characters read up to the end of the first run of
+/// `window_size` distinct characters.
+pub fn find_marker_position(buffer: &[char], window_size: usize) -> Option<usize> {
    let sequences = buffer.windows(window_size);
 
    for (marker, sequence) in sequences.enumerate() {
@@ -16,12 +37,60 @@ fn find_marker_position(buffer: &Vec<char>, window_size: usize) -> Option<usize>
    None
 }
 
-pub fn solve_part_one() -> usize {
-   let buffer = parse_input();
-   find_marker_position(&buffer, 4).unwrap()
+pub struct Day06;
+
+impl Solution for Day06 {
+   const DAY: u8 = 6;
+
+   type Input = Vec<char>;
+   type PartOne = usize;
+   type PartTwo = usize;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(buffer: &Self::Input) -> usize {
+      find_marker_position(buffer, 4).expect("checked while parsing")
+   }
+
+   fn part_two(buffer: &Self::Input) -> usize {
+      find_marker_position(buffer, 14).expect("checked while parsing")
+   }
 }
 
-pub fn solve_part_two() -> usize {
-   let buffer = parse_input();
-   find_marker_position(&buffer, 14).unwrap()
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   // Each example buffer, with its part one and part two markers.
+   const EXAMPLES: [(&str, usize, usize); 5] = [
+      ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+      ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+      ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+      ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+      ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+   ];
+
+   #[test]
+   fn part_one_examples() {
+      for (example, marker, _) in EXAMPLES {
+         let buffer = Day06::parse(example).unwrap();
+         assert_eq!(Day06::part_one(&buffer), marker, "{}", example);
+      }
+   }
+
+   #[test]
+   fn part_two_examples() {
+      for (example, _, marker) in EXAMPLES {
+         let buffer = Day06::parse(example).unwrap();
+         assert_eq!(Day06::part_two(&buffer), marker, "{}", example);
+      }
+   }
+
+   #[test]
+   fn rejects_buffers_without_a_marker() {
+      let error = Day06::parse("aaaa").unwrap_err();
+      assert_eq!((error.line, error.column), (1, 5));
+   }
 }
@@ -1,14 +1,22 @@
-use std::{cell::RefCell, fmt::Display, rc::Rc};
+//! Day 7: No Space Left On Device
+
+use std::{
+   fmt::{self, Debug, Display},
+   sync::{Arc, LazyLock, RwLock, Weak},
+};
 
 use regex::Regex;
 
+use crate::{error::ParseError, solution::Solution};
+
 use ChangeDirectory::*;
 use InodeKind::*;
 
 #[derive(Clone)]
 struct Inode<'a> {
    kind: InodeKind<'a>,
-   parent: Option<Rc<RefCell<Inode<'a>>>>,
+   // Weak, so that a directory and its children don't keep each other alive.
+   parent: Option<Weak<RwLock<Inode<'a>>>>,
    path: Option<String>,
    name: String,
    // Invariant: Because the directory size is a computed property, this
@@ -21,12 +29,12 @@ struct Inode<'a> {
 enum InodeKind<'a> {
    File,
    Directory {
-      children: Vec<Rc<RefCell<Inode<'a>>>>,
+      children: Vec<Arc<RwLock<Inode<'a>>>>,
    },
 }
 
 impl<'a> Inode<'a> {
-   fn get_children(&self) -> Vec<Rc<RefCell<Inode<'a>>>> {
+   fn get_children(&self) -> Vec<Arc<RwLock<Inode<'a>>>> {
       match self.kind {
          File { .. } => vec![],
          Directory { ref children, .. } => children.clone(),
@@ -40,9 +48,26 @@ impl<'a> Inode<'a> {
       }
    }
 
+   /// Appends the inode and everything below it to `tree`, one per line,
+   /// indented by depth.
+   fn draw(&self, depth: usize, tree: &mut String) {
+      let kind = if self.is_directory() { "dir" } else { "file" };
+      *tree += &format!(
+         "{}- {} ({}, size={})\n",
+         "  ".repeat(depth),
+         self.name,
+         kind,
+         self.size
+      );
+
+      for child in self.get_children() {
+         child.read().unwrap().draw(depth + 1, tree);
+      }
+   }
+
    fn iter(&self) -> InodeIter<'a> {
       InodeIter {
-         current: Rc::new(RefCell::new(self.clone())),
+         current: Arc::new(RwLock::new(self.clone())),
          parent: None,
          children: self.get_children().clone(),
          seen: false,
@@ -63,7 +88,7 @@ impl<'a> Display for Inode<'a> {
          )?;
 
          for c in children {
-            let c = c.borrow();
+            let c = c.read().unwrap();
             write!(f, "{}", *c)?;
          }
       }
@@ -72,16 +97,41 @@ impl<'a> Display for Inode<'a> {
    }
 }
 
+// Leaves out the parent, which would otherwise show up in every child.
+impl<'a> Debug for Inode<'a> {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self.kind {
+         File => f
+            .debug_struct("File")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .finish(),
+         Directory { ref children } => f
+            .debug_struct("Directory")
+            .field("name", &self.name)
+            .field("size", &self.size)
+            .field(
+               "children",
+               &children
+                  .iter()
+                  .map(|child| child.read().unwrap())
+                  .collect::<Vec<_>>(),
+            )
+            .finish(),
+      }
+   }
+}
+
 #[derive(Clone)]
 struct InodeIter<'a> {
-   current: Rc<RefCell<Inode<'a>>>,
+   current: Arc<RwLock<Inode<'a>>>,
    parent: Option<Box<InodeIter<'a>>>,
-   children: Vec<Rc<RefCell<Inode<'a>>>>,
+   children: Vec<Arc<RwLock<Inode<'a>>>>,
    seen: bool,
 }
 
 impl<'a> Iterator for InodeIter<'a> {
-   type Item = Rc<RefCell<Inode<'a>>>;
+   type Item = Arc<RwLock<Inode<'a>>>;
 
    fn next(&mut self) -> Option<Self::Item> {
       // We may visit the same directory multiple times during traversal.
@@ -91,7 +141,7 @@ impl<'a> Iterator for InodeIter<'a> {
          return Some(self.current.clone());
       }
 
-      match self.children.clone().get(0) {
+      match self.children.clone().first() {
          None => match self.parent.take() {
             Some(parent) => {
                *self = *parent;
@@ -101,29 +151,31 @@ impl<'a> Iterator for InodeIter<'a> {
          },
          Some(inode) => {
             let item = self.children.remove(0);
-            match inode.clone().borrow().kind {
-               File { .. } => Some(item),
-               Directory { .. } => {
-                  let current = self.clone();
-                  *self = item.borrow().iter();
-                  self.parent = Some(Box::new(current));
-                  self.next()
-               }
+            // Release the lock on the inode before descending into it.
+            let is_directory = inode.read().unwrap().is_directory();
+            if is_directory {
+               let current = self.clone();
+               *self = item.read().unwrap().iter();
+               self.parent = Some(Box::new(current));
+               self.next()
+            } else {
+               Some(item)
             }
          }
       }
    }
 }
 
+/// A tree of directories and files, rooted at `/`.
 #[derive(Clone)]
-struct FileSystem<'a> {
-   tree: Rc<RefCell<Inode<'a>>>,
+pub struct FileSystem<'a> {
+   tree: Arc<RwLock<Inode<'a>>>,
 }
 
 impl<'a> FileSystem<'a> {
-   fn new() -> Self {
+   pub fn new() -> Self {
       FileSystem {
-         tree: Rc::new(RefCell::new(Inode {
+         tree: Arc::new(RwLock::new(Inode {
             kind: Directory { children: vec![] },
             parent: None,
             path: Some(String::from("/")),
@@ -132,24 +184,75 @@ impl<'a> FileSystem<'a> {
          })),
       }
    }
+
+   /// The total size of every file in the file system.
+   pub fn size(&self) -> usize {
+      self.tree.read().unwrap().size
+   }
+
+   /// Draws the tree the way the puzzle does, e.g. `- a (dir, size=94853)`.
+   pub fn draw_tree(&self) -> String {
+      let mut tree = String::new();
+      self.tree.read().unwrap().draw(0, &mut tree);
+      tree
+   }
+
+   /// The total size of each directory, including the root.
+   pub fn directory_sizes(&self) -> Vec<usize> {
+      self
+         .tree
+         .read()
+         .unwrap()
+         .iter()
+         .filter(|file| file.read().unwrap().is_directory())
+         .map(|file| file.read().unwrap().size)
+         .collect()
+   }
+}
+
+impl<'a> Debug for FileSystem<'a> {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.debug_struct("FileSystem")
+         .field("root", &*self.tree.read().unwrap())
+         .finish()
+   }
+}
+
+impl<'a> Default for FileSystem<'a> {
+   fn default() -> Self {
+      Self::new()
+   }
+}
+
+impl<'a> Display for FileSystem<'a> {
+   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      write!(f, "{}", self.tree.read().unwrap())
+   }
 }
 
-struct FileSystemCursor<'a> {
+/// Walks a [`FileSystem`] like a shell, creating files in the current
+/// directory.
+pub struct FileSystemCursor<'a> {
    cwd: String,
-   file_system: Rc<RefCell<Inode<'a>>>,
-   cursor: Rc<RefCell<Inode<'a>>>,
+   file_system: Arc<RwLock<Inode<'a>>>,
+   cursor: Arc<RwLock<Inode<'a>>>,
 }
 
 impl<'a> FileSystemCursor<'a> {
-   fn new(file_system: FileSystem<'a>) -> Self {
+   pub fn new(file_system: FileSystem<'a>) -> Self {
       FileSystemCursor {
-         cwd: file_system.tree.clone().borrow().name.clone(),
+         cwd: file_system.tree.clone().read().unwrap().name.clone(),
          file_system: file_system.tree.clone(),
          cursor: file_system.tree.clone(),
       }
    }
 
-   fn change_directory(&mut self, command: ChangeDirectory) {
+   /// The absolute path of the current directory, ending in `/`.
+   pub fn cwd(&self) -> &str {
+      &self.cwd
+   }
+
+   pub fn change_directory(&mut self, command: ChangeDirectory<'_>) {
       match command {
          Root => {
             self.cwd = "/".to_owned();
@@ -158,70 +261,67 @@ impl<'a> FileSystemCursor<'a> {
 
          In(file_name) => {
             let cursor = self.cursor.clone();
-            let current_directory = cursor.borrow();
+            let current_directory = cursor.read().unwrap();
 
-            if let Directory { ref children, .. } = (*current_directory).kind {
+            if let Directory { ref children, .. } = current_directory.kind {
                let subdirectory = children.iter().find(|file| {
-                  let file = file.borrow();
+                  let file = file.read().unwrap();
                   file.is_directory() && file.name == file_name
                });
 
                if let Some(subdirectory) = subdirectory {
                   self.cursor = subdirectory.clone();
-                  self.cwd = self.cwd.clone() + &subdirectory.borrow().name + "/";
+                  self.cwd = self.cwd.clone() + &subdirectory.read().unwrap().name + "/";
                }
             }
          }
 
          Out => {
-            match self.cursor.clone().borrow().parent.clone() {
-               Some(parent) => {
-                  self.cursor = parent.clone();
-
-                  let parts: Vec<_> = self.cwd.split_terminator('/').collect();
-                  let parts_count = parts.len();
-
-                  if parts_count == 0 {
-                     self.cwd = String::from("/");
-                  } else {
-                     self.cwd = parts
-                        .into_iter()
-                        .take(parts_count - 1)
-                        .collect::<Vec<_>>()
-                        .join("/")
-                        + "/";
-                  }
+            // `cd ..` in the root directory does nothing.
+            let parent = self.cursor.read().unwrap().parent.clone();
+            if let Some(parent) = parent.and_then(|parent| parent.upgrade()) {
+               self.cursor = parent;
+
+               let parts: Vec<_> = self.cwd.split_terminator('/').collect();
+               let parts_count = parts.len();
+
+               if parts_count == 0 {
+                  self.cwd = String::from("/");
+               } else {
+                  self.cwd = parts
+                     .into_iter()
+                     .take(parts_count - 1)
+                     .collect::<Vec<_>>()
+                     .join("/")
+                     + "/";
                }
-
-               // Cannot `cd ..` in root directory
-               None => (),
             }
          }
       }
    }
 
-   fn create_file(&mut self, file: Rc<RefCell<Inode<'a>>>) {
+   fn create_file(&mut self, file: Arc<RwLock<Inode<'a>>>) {
       if let Inode {
          ref mut parent,
          ref mut size,
          kind: Directory { ref mut children },
          ..
-      } = *self.cursor.borrow_mut()
+      } = *self.cursor.write().unwrap()
       {
          // Update directory size for parent directory.
-         *size += file.borrow().size;
-         let mut iter_cursor = parent.clone();
+         *size += file.read().unwrap().size;
+         let mut iter_cursor = parent.as_ref().and_then(Weak::upgrade);
          while let Some(inode) = iter_cursor {
-            let mut inode = inode.borrow_mut();
-            let new_size = inode.size + file.borrow().size;
+            let mut inode = inode.write().unwrap();
+            let new_size = inode.size + file.read().unwrap().size;
             inode.size = new_size;
-            iter_cursor = inode.parent.clone();
+            iter_cursor = inode.parent.as_ref().and_then(Weak::upgrade);
          }
 
          // Link the file to the filesystem tree.
-         let absolute_path = self.cwd.clone() + &file.borrow().name;
-         file.borrow_mut().path = Some(absolute_path);
-         file.borrow_mut().parent = Some(self.cursor.clone());
+         let absolute_path = self.cwd.clone() + &file.read().unwrap().name;
+         file.write().unwrap().path = Some(absolute_path);
+         file.write().unwrap().parent = Some(Arc::downgrade(&self.cursor));
          children.push(file);
 
          return;
@@ -230,22 +330,49 @@ impl<'a> FileSystemCursor<'a> {
       // Invariant: Filesystem cursors can only point to directories.
       unreachable!()
    }
+
+   /// Creates an empty directory in the current directory.
+   pub fn add_directory(&mut self, name: &str) {
+      self.create_file(Arc::new(RwLock::new(Inode {
+         kind: Directory { children: vec![] },
+         parent: None,
+         path: None,
+         name: name.to_owned(),
+         size: 0,
+      })));
+   }
+
+   /// Creates a file in the current directory.
+   pub fn add_file(&mut self, name: &str, size: usize) {
+      self.create_file(Arc::new(RwLock::new(Inode {
+         kind: File,
+         parent: None,
+         path: None,
+         name: name.to_owned(),
+         size,
+      })));
+   }
 }
 
 #[derive(Debug)]
-enum ChangeDirectory {
+pub enum ChangeDirectory<'a> {
    Root,
-   In(&'static str),
+   In(&'a str),
    Out,
 }
 
 #[derive(Debug)]
-enum Command {
+enum Command<'a> {
    ListDirectory,
-   ChangeDirectory(ChangeDirectory),
+   ChangeDirectory(ChangeDirectory<'a>),
 }
 
-fn parse_command(input: &'static str) -> Option<Command> {
+// Compiled once, since they are matched against every line of the transcript.
+static CD_IN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$ cd (.*)").unwrap());
+static DIRECTORY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"dir (.*)").unwrap());
+static FILE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(.*) (.*)").unwrap());
+
+fn parse_command(input: &str) -> Option<Command<'_>> {
    if input == "$ ls" {
       return Some(Command::ListDirectory);
    }
@@ -258,8 +385,7 @@ fn parse_command(input: &'static str) -> Option<Command> {
       return Some(Command::ChangeDirectory(Out));
    }
 
-   let cd_in = Regex::new(r"\$ cd (.*)").unwrap();
-   if let Some(captures) = cd_in.captures(input) {
+   if let Some(captures) = CD_IN.captures(input) {
       return captures
          .get(1)
          .map(|filename| Command::ChangeDirectory(In(filename.as_str())));
@@ -268,97 +394,301 @@ fn parse_command(input: &'static str) -> Option<Command> {
    None
 }
 
-fn parse_inode(input: &'static str) -> Option<Inode> {
-   if input.starts_with("$") {
-      return None;
-   }
+fn parse_inode(line_number: usize, input: &str) -> Result<Inode<'static>, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day07::DAY, line_number, input, text, message);
 
-   let directory = Regex::new(r"dir (.*)").unwrap();
-   if let Some(captures) = directory.captures(input) {
-      return captures.get(1).map(|directory_name| Inode {
+   if let Some(captures) = DIRECTORY.captures(input) {
+      return Ok(Inode {
          kind: Directory { children: vec![] },
          parent: None,
          path: None,
-         name: directory_name.as_str().to_owned(),
+         name: captures[1].to_owned(),
          size: 0,
       });
    }
 
-   let file = Regex::new(r"(.*) (.*)").unwrap();
-   if let Some(captures) = file.captures(input) {
-      return captures.get(1).and_then(|size| {
-         captures.get(2).and_then(|file_name| {
-            Some(Inode {
-               kind: File,
-               parent: None,
-               path: None,
-               name: file_name.as_str().to_owned(),
-               size: size.as_str().parse().unwrap(),
-            })
-         })
+   if let Some(captures) = FILE.captures(input) {
+      let size = captures.get(1).unwrap().as_str();
+
+      return Ok(Inode {
+         kind: File,
+         parent: None,
+         path: None,
+         name: captures[2].to_owned(),
+         size: size
+            .parse()
+            .map_err(|_| error(size, "expected a file size"))?,
       });
    }
 
-   None
+   Err(error(input, "expected a command, a directory or a file"))
 }
 
-fn build_file_system() -> FileSystem<'static> {
-   let console_output = include_str!("../input/day07.txt")
-      .lines()
-      .collect::<Vec<&'static str>>();
+fn build_file_system(input: &str) -> Result<FileSystem<'static>, ParseError> {
+   let console_output = input.lines().collect::<Vec<&str>>();
 
    let file_system = FileSystem::new();
    let mut cursor = FileSystemCursor::new(file_system.clone());
 
-   for line in console_output {
-      if let Some(command) = parse_command(line) {
-         match command {
-            Command::ListDirectory => (),
-            Command::ChangeDirectory(command) => cursor.change_directory(command),
+   for (index, line) in console_output.into_iter().enumerate() {
+      if line.starts_with('$') {
+         match parse_command(line) {
+            Some(Command::ListDirectory) => (),
+            Some(Command::ChangeDirectory(command)) => cursor.change_directory(command),
+            None => {
+               return Err(ParseError::new(
+                  Day07::DAY,
+                  index + 1,
+                  line,
+                  line,
+                  "expected `$ cd <directory>` or `$ ls`",
+               ))
+            }
+         }
+      } else {
+         let inode = parse_inode(index + 1, line)?;
+
+         // No directory is larger than the root, so if its size doesn't
+         // overflow, neither does any other.
+         if file_system.size().checked_add(inode.size).is_none() {
+            return Err(ParseError::new(
+               Day07::DAY,
+               index + 1,
+               line,
+               line,
+               "the files add up to more than the file system can hold",
+            ));
          }
-      }
 
-      if let Some(inode) = parse_inode(line) {
-         cursor.create_file(Rc::new(RefCell::new(inode)));
+         cursor.create_file(Arc::new(RwLock::new(inode)));
       }
    }
 
-   file_system.clone()
+   Ok(file_system.clone())
 }
 
-pub fn solve_part_one() -> usize {
-   build_file_system()
-      .tree
-      .borrow()
-      .iter()
-      .filter(|file| file.borrow().is_directory())
-      .map(|file| file.borrow().size)
-      .filter(|&size| size <= 100000)
-      .sum()
+pub struct Day07;
+
+impl Solution for Day07 {
+   const DAY: u8 = 7;
+
+   type Input = FileSystem<'static>;
+   type PartOne = usize;
+   type PartTwo = usize;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      build_file_system(input)
+   }
+
+   fn part_one(file_system: &Self::Input) -> usize {
+      file_system
+         .directory_sizes()
+         .into_iter()
+         .filter(|&size| size <= 100000)
+         .sum()
+   }
+
+   fn part_two(file_system: &Self::Input) -> usize {
+      const TOTAL_DISK_SPACE: usize = 70000000;
+      const TARGET_FREE_DISK_SPACE: usize = 30000000;
+
+      let current_disk_space = file_system.size();
+      let disk_space_to_free =
+         current_disk_space.saturating_sub(TOTAL_DISK_SPACE - TARGET_FREE_DISK_SPACE);
+
+      // Nothing needs to be deleted if there is already enough free space.
+      if disk_space_to_free == 0 {
+         return 0;
+      }
+
+      // Deleting the root frees everything, so it qualifies if nothing
+      // smaller does.
+      file_system
+         .directory_sizes()
+         .into_iter()
+         .filter(|&size| size >= disk_space_to_free)
+         .min()
+         .unwrap_or(current_disk_space)
+   }
+
+   fn visualize(file_system: &Self::Input) -> Option<String> {
+      Some(file_system.draw_tree())
+   }
 }
 
-pub fn solve_part_two() -> usize {
-   const TOTAL_DISK_SPACE: usize = 70000000;
-   const TARGET_FREE_DISK_SPACE: usize = 30000000;
-
-   let file_system = build_file_system();
-   let current_disk_space = file_system.tree.borrow().size;
-   let disk_space_to_free = current_disk_space - (TOTAL_DISK_SPACE - TARGET_FREE_DISK_SPACE);
-
-   let mut directory_sizes = build_file_system()
-      .tree
-      .borrow()
-      .iter()
-      .filter(|file| file.borrow().is_directory())
-      .map(|file| file.borrow().size)
-      .collect::<Vec<_>>();
-
-   directory_sizes.sort();
-   for &directory_size in directory_sizes.iter() {
-      if directory_size >= disk_space_to_free {
-         return directory_size;
+#[cfg(test)]
+mod tests {
+   use proptest::prelude::*;
+
+   use super::*;
+
+   const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+   #[test]
+   fn part_one_example() {
+      let file_system = Day07::parse(EXAMPLE).unwrap();
+      assert_eq!(Day07::part_one(&file_system), 95437);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let file_system = Day07::parse(EXAMPLE).unwrap();
+      assert_eq!(Day07::part_two(&file_system), 24933642);
+   }
+
+   #[test]
+   fn part_two_frees_nothing_on_a_small_disk() {
+      let file_system = Day07::parse("$ cd /\n$ ls\n100 a").unwrap();
+      assert_eq!(Day07::part_two(&file_system), 0);
+   }
+
+   #[test]
+   fn rejects_file_sizes_that_overflow() {
+      let transcript =
+         "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n18446744073709551615 b\n$ cd ..\n$ ls\n1 c";
+      let error = Day07::parse(transcript).err().unwrap();
+      assert_eq!((error.line, error.text.as_str()), (9, "1 c"));
+   }
+
+   #[test]
+   fn dropping_the_file_system_frees_every_inode() {
+      let file_system = Day07::parse(EXAMPLE).unwrap();
+      let inodes = file_system
+         .tree
+         .read()
+         .unwrap()
+         .iter()
+         .skip(1)
+         .map(|inode| Arc::downgrade(&inode))
+         .collect::<Vec<_>>();
+
+      drop(file_system);
+      assert!(inodes.iter().all(|inode| inode.upgrade().is_none()));
+   }
+
+   /// A directory to be turned into a shell transcript.
+   #[derive(Clone, Debug)]
+   struct Tree {
+      files: Vec<usize>,
+      directories: Vec<Tree>,
+   }
+
+   fn tree() -> impl Strategy<Value = Tree> {
+      let files = || prop::collection::vec(prop_oneof![1..100_000usize, 1..10_000_000usize], 0..4);
+
+      files()
+         .prop_map(|files| Tree {
+            files,
+            directories: vec![],
+         })
+         .prop_recursive(4, 32, 4, move |directory| {
+            (files(), prop::collection::vec(directory, 0..4))
+               .prop_map(|(files, directories)| Tree { files, directories })
+         })
+   }
+
+   /// Lists the directory, then visits each subdirectory in turn.
+   fn write_transcript(tree: &Tree, transcript: &mut String) {
+      *transcript += "$ ls\n";
+
+      for (index, size) in tree.files.iter().enumerate() {
+         *transcript += &format!("{} f{}.txt\n", size, index);
+      }
+
+      for index in 0..tree.directories.len() {
+         *transcript += &format!("dir d{}\n", index);
+      }
+
+      for (index, directory) in tree.directories.iter().enumerate() {
+         *transcript += &format!("$ cd d{}\n", index);
+         write_transcript(directory, transcript);
+         *transcript += "$ cd ..\n";
       }
    }
 
-   unreachable!()
+   /// Naive reference: collects the size of every directory, returning the
+   /// size of this one.
+   fn reference_sizes(tree: &Tree, sizes: &mut Vec<usize>) -> usize {
+      let size = tree.files.iter().sum::<usize>()
+         + tree
+            .directories
+            .iter()
+            .map(|directory| reference_sizes(directory, sizes))
+            .sum::<usize>();
+
+      sizes.push(size);
+      size
+   }
+
+   /// Whether every directory's size is the sum of its children's sizes.
+   fn sizes_are_consistent(inode: &Inode) -> bool {
+      let children = inode.get_children();
+      let sum = children
+         .iter()
+         .map(|child| child.read().unwrap().size)
+         .sum::<usize>();
+
+      !inode.is_directory()
+         || (inode.size == sum
+            && children
+               .iter()
+               .all(|child| sizes_are_consistent(&child.read().unwrap())))
+   }
+
+   proptest! {
+      #[test]
+      fn directory_sizes_match_the_reference(tree in tree()) {
+         let mut transcript = String::from("$ cd /\n");
+         write_transcript(&tree, &mut transcript);
+         let file_system = Day07::parse(&transcript).unwrap();
+
+         prop_assert!(sizes_are_consistent(&file_system.tree.read().unwrap()));
+
+         let mut expected = vec![];
+         let total = reference_sizes(&tree, &mut expected);
+         prop_assert_eq!(file_system.size(), total);
+
+         let mut actual = file_system.directory_sizes();
+         actual.sort();
+         expected.sort();
+         prop_assert_eq!(&actual, &expected);
+
+         prop_assert_eq!(
+            Day07::part_one(&file_system),
+            expected.iter().filter(|&&size| size <= 100000).sum::<usize>()
+         );
+
+         let part_two = match total.checked_sub(40000000) {
+            Some(to_free) if to_free > 0 => {
+               *expected.iter().find(|&&size| size >= to_free).unwrap()
+            }
+            _ => 0,
+         };
+         prop_assert_eq!(Day07::part_two(&file_system), part_two);
+      }
+   }
 }
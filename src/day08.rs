@@ -1,23 +1,48 @@
+//! Day 8: Treetop Tree House
+
+use std::fmt::{self, Debug};
+
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Debug, Clone, Copy)]
-struct Point {
-   row: usize,
-   col: usize,
+pub struct Point {
+   pub row: usize,
+   pub col: usize,
 }
 
 #[derive(Debug)]
-struct Tree {
-   height: u32,
+pub struct Tree {
+   pub height: u32,
 }
 
-struct TreePatch {
+/// A rectangular grid of trees, indexed by row and then column.
+pub struct TreePatch {
    grid: Vec<Vec<Tree>>,
 }
 
+// Shows each row as its tree heights, like the input, rather than one
+// `Tree` per line.
+impl Debug for TreePatch {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      let rows = self
+         .grid
+         .iter()
+         .map(|row| row.iter().map(|tree| tree.height.to_string()).collect())
+         .collect::<Vec<String>>();
+
+      f.debug_struct("TreePatch").field("grid", &rows).finish()
+   }
+}
+
 impl TreePatch {
-   fn new(grid: Vec<Vec<Tree>>) -> Self {
+   pub fn new(grid: Vec<Vec<Tree>>) -> Self {
       TreePatch { grid }
    }
 
+   pub fn grid(&self) -> &[Vec<Tree>] {
+      &self.grid
+   }
+
    fn left_trees(&self, location: Point) -> Vec<&Tree> {
       self.grid[location.row]
          .iter()
@@ -53,22 +78,58 @@ impl TreePatch {
    }
 }
 
-fn parse_input() -> TreePatch {
-   TreePatch::new(
-      include_str!("../input/day08.txt")
-         .lines()
-         .map(|row| {
-            row.chars()
-               .map(|height| Tree {
-                  height: height.to_digit(10).unwrap(),
-               })
-               .collect()
-         })
-         .collect(),
-   )
+fn parse_row(line_number: usize, line: &str) -> Result<Vec<Tree>, ParseError> {
+   line
+      .char_indices()
+      .map(|(index, height)| {
+         height
+            .to_digit(10)
+            .map(|height| Tree { height })
+            .ok_or_else(|| {
+               let text = &line[index..index + height.len_utf8()];
+               ParseError::new(
+                  Day08::DAY,
+                  line_number,
+                  line,
+                  text,
+                  "expected a tree height (0-9)",
+               )
+            })
+      })
+      .collect()
 }
 
-fn is_visible(patch: &TreePatch, location: Point) -> bool {
+fn parse_input(input: &str) -> Result<TreePatch, ParseError> {
+   let mut grid: Vec<Vec<Tree>> = vec![];
+
+   for (index, line) in input.lines().enumerate() {
+      let row = parse_row(index + 1, line)?;
+
+      // The patch must be rectangular, or looking up and down a column
+      // would run off the end of a shorter row.
+      if let Some(first_row) = grid.first() {
+         if row.len() != first_row.len() {
+            return Err(ParseError::new(
+               Day08::DAY,
+               index + 1,
+               line,
+               line,
+               format!(
+                  "expected a row of {} trees, found {}",
+                  first_row.len(),
+                  row.len()
+               ),
+            ));
+         }
+      }
+
+      grid.push(row);
+   }
+
+   Ok(TreePatch::new(grid))
+}
+
+pub fn is_visible(patch: &TreePatch, location: Point) -> bool {
    // All exterior trees are visible.
    if location.row == 0
       || location.row == patch.grid.len() - 1
@@ -90,7 +151,7 @@ fn is_visible(patch: &TreePatch, location: Point) -> bool {
       || is_visible_over(patch.bottom_trees(location))
 }
 
-fn get_scenic_score(patch: &TreePatch, location: Point) -> usize {
+pub fn get_scenic_score(patch: &TreePatch, location: Point) -> usize {
    let tree = &patch.grid[location.row][location.col];
 
    let get_viewing_distance = |trees: Vec<&Tree>| {
@@ -112,33 +173,69 @@ fn get_scenic_score(patch: &TreePatch, location: Point) -> usize {
       * get_viewing_distance(patch.bottom_trees(location))
 }
 
-pub fn solve_part_one() -> usize {
-   let patch = parse_input();
-   let mut visible_trees = 0;
+pub struct Day08;
+
+impl Solution for Day08 {
+   const DAY: u8 = 8;
+
+   type Input = TreePatch;
+   type PartOne = usize;
+   type PartTwo = usize;
 
-   for row in 0..patch.grid.len() {
-      for col in 0..patch.grid[row].len() {
-         if is_visible(&patch, Point { row, col }) {
-            visible_trees += 1;
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(patch: &Self::Input) -> usize {
+      let mut visible_trees = 0;
+
+      for row in 0..patch.grid.len() {
+         for col in 0..patch.grid[row].len() {
+            if is_visible(patch, Point { row, col }) {
+               visible_trees += 1;
+            }
          }
       }
-   }
 
-   visible_trees
-}
+      visible_trees
+   }
 
-pub fn solve_part_two() -> usize {
-   let patch = parse_input();
-   let mut highest_scenic_score = 0;
+   fn part_two(patch: &Self::Input) -> usize {
+      let mut highest_scenic_score = 0;
 
-   for row in 0..patch.grid.len() {
-      for col in 0..patch.grid[row].len() {
-         let scenic_score = get_scenic_score(&patch, Point { row, col });
-         if scenic_score > highest_scenic_score {
-            highest_scenic_score = scenic_score;
+      for row in 0..patch.grid.len() {
+         for col in 0..patch.grid[row].len() {
+            let scenic_score = get_scenic_score(patch, Point { row, col });
+            if scenic_score > highest_scenic_score {
+               highest_scenic_score = scenic_score;
+            }
          }
       }
+
+      highest_scenic_score
    }
+}
 
-   highest_scenic_score
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   const EXAMPLE: &str = "\
+30373
+25512
+65332
+33549
+35390";
+
+   #[test]
+   fn part_one_example() {
+      let patch = Day08::parse(EXAMPLE).unwrap();
+      assert_eq!(Day08::part_one(&patch), 21);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let patch = Day08::parse(EXAMPLE).unwrap();
+      assert_eq!(Day08::part_two(&patch), 8);
+   }
 }
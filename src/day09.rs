@@ -1,20 +1,26 @@
+//! Day 9: Rope Bridge
+
 use std::{collections::HashSet, fmt::Display, thread::sleep, time::Duration};
 
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Point {
-   x: i32,
-   y: i32,
+pub struct Point {
+   pub x: i32,
+   pub y: i32,
 }
 
+/// A rope being pulled around by its head, and every point its tail has
+/// visited.
 #[derive(Debug)]
-struct SimulationState {
+pub struct SimulationState {
    visited: HashSet<Point>,
    head: Point,
    tail: Vec<Point>,
 }
 
 impl SimulationState {
-   fn new(rope_size: usize) -> Self {
+   pub fn new(rope_size: usize) -> Self {
       if rope_size < 2 {
          panic!("Rope size must be at least 2");
       }
@@ -25,6 +31,20 @@ impl SimulationState {
          tail: vec![Point { x: 0, y: 0 }; rope_size - 1],
       }
    }
+
+   pub fn head(&self) -> Point {
+      self.head
+   }
+
+   /// Every knot after the head, in order.
+   pub fn tail(&self) -> &[Point] {
+      &self.tail
+   }
+
+   /// Every point the last knot has visited.
+   pub fn visited(&self) -> &HashSet<Point> {
+      &self.visited
+   }
 }
 
 impl Display for SimulationState {
@@ -66,7 +86,7 @@ impl Display for SimulationState {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
    Up,
    Down,
    Left,
@@ -74,29 +94,44 @@ enum Direction {
 }
 
 #[derive(Debug, Clone, Copy)]
-struct Motion {
-   direction: Direction,
-   distance: usize,
+pub struct Motion {
+   pub direction: Direction,
+   pub distance: usize,
 }
 
-fn parse_input() -> Vec<Motion> {
-   include_str!("../input/day09.txt")
+fn parse_motion(line_number: usize, line: &str) -> Result<Motion, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day09::DAY, line_number, line, text, message);
+
+   let (direction, distance) = line
+      .split_once(' ')
+      .ok_or_else(|| error(line, "expected a direction and a distance"))?;
+
+   Ok(Motion {
+      direction: match direction {
+         "U" => Direction::Up,
+         "D" => Direction::Down,
+         "L" => Direction::Left,
+         "R" => Direction::Right,
+         _ => return Err(error(direction, "expected a direction (U, D, L or R)")),
+      },
+      distance: distance
+         .parse()
+         .map_err(|_| error(distance, "expected a distance"))?,
+   })
+}
+
+fn parse_input(input: &str) -> Result<Vec<Motion>, ParseError> {
+   input
       .lines()
-      .map(|line| line.split(' ').collect::<Vec<_>>())
-      .map(|tokens| Motion {
-         direction: match tokens[0] {
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            _ => unreachable!(),
-         },
-         distance: tokens[1].parse().unwrap(),
-      })
+      .enumerate()
+      .map(|(index, line)| parse_motion(index + 1, line))
       .collect()
 }
 
-fn simulate(motion: Motion, state: &mut SimulationState, debug: bool) {
+/// Moves the head of the rope, dragging the rest of the rope along. When
+/// `debug` is set, the rope is drawn after every step.
+pub fn simulate(motion: Motion, state: &mut SimulationState, debug: bool) {
    let (dx, dy) = match motion.direction {
       Direction::Up => (0, -1),
       Direction::Down => (0, 1),
@@ -136,24 +171,173 @@ fn simulate(motion: Motion, state: &mut SimulationState, debug: bool) {
    }
 }
 
-pub fn solve_part_one() -> usize {
-   let motions = parse_input();
-   let mut state = SimulationState::new(2);
+/// Pulls a rope with `rope_size` knots through every motion.
+pub fn simulate_all(motions: &[Motion], rope_size: usize) -> SimulationState {
+   let mut state = SimulationState::new(rope_size);
 
-   for motion in motions {
+   for &motion in motions {
       simulate(motion, &mut state, false);
    }
 
-   state.visited.len()
+   state
 }
 
-pub fn solve_part_two() -> usize {
-   let motions = parse_input();
-   let mut state = SimulationState::new(10);
+pub struct Day09;
 
-   for motion in motions {
-      simulate(motion, &mut state, false);
+impl Solution for Day09 {
+   const DAY: u8 = 9;
+
+   type Input = Vec<Motion>;
+   type PartOne = usize;
+   type PartTwo = usize;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(motions: &Self::Input) -> usize {
+      simulate_all(motions, 2).visited.len()
+   }
+
+   fn part_two(motions: &Self::Input) -> usize {
+      simulate_all(motions, 10).visited.len()
+   }
+
+   fn visualize(motions: &Self::Input) -> Option<String> {
+      Some(simulate_all(motions, 10).to_string())
    }
+}
 
-   state.visited.len()
+#[cfg(test)]
+mod tests {
+   use proptest::prelude::*;
+
+   use super::*;
+
+   const EXAMPLE: &str = "\
+R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+   const LARGER_EXAMPLE: &str = "\
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+   #[test]
+   fn part_one_example() {
+      let motions = Day09::parse(EXAMPLE).unwrap();
+      assert_eq!(Day09::part_one(&motions), 13);
+   }
+
+   #[test]
+   fn part_two_example() {
+      let motions = Day09::parse(EXAMPLE).unwrap();
+      assert_eq!(Day09::part_two(&motions), 1);
+   }
+
+   #[test]
+   fn part_two_larger_example() {
+      let motions = Day09::parse(LARGER_EXAMPLE).unwrap();
+      assert_eq!(Day09::part_two(&motions), 36);
+   }
+
+   fn motion() -> impl Strategy<Value = Motion> {
+      let direction = prop_oneof![
+         Just(Direction::Up),
+         Just(Direction::Down),
+         Just(Direction::Left),
+         Just(Direction::Right),
+      ];
+
+      (direction, 0..20usize).prop_map(|(direction, distance)| Motion {
+         direction,
+         distance,
+      })
+   }
+
+   /// Naive reference: moves the head one step at a time, then lets each
+   /// knot that no longer touches the one in front of it step towards it.
+   fn reference_visited(motions: &[Motion], rope_size: usize) -> HashSet<(i32, i32)> {
+      let mut rope = vec![(0i32, 0i32); rope_size];
+      let mut visited = HashSet::new();
+
+      for motion in motions {
+         for _ in 0..motion.distance {
+            match motion.direction {
+               Direction::Up => rope[0].1 -= 1,
+               Direction::Down => rope[0].1 += 1,
+               Direction::Left => rope[0].0 -= 1,
+               Direction::Right => rope[0].0 += 1,
+            }
+
+            for index in 1..rope_size {
+               let (front_x, front_y) = rope[index - 1];
+               let (x, y) = rope[index];
+               let touching = (front_x - x).abs() <= 1 && (front_y - y).abs() <= 1;
+
+               if !touching {
+                  rope[index] = (
+                     x + (front_x - x).clamp(-1, 1),
+                     y + (front_y - y).clamp(-1, 1),
+                  );
+               }
+            }
+
+            visited.insert(rope[rope_size - 1]);
+         }
+      }
+
+      visited
+   }
+
+   proptest! {
+      #[test]
+      fn knots_never_drift_apart(
+         motions in prop::collection::vec(motion(), 0..50),
+         rope_size in 2..12usize,
+      ) {
+         let mut state = SimulationState::new(rope_size);
+
+         for motion in motions {
+            simulate(motion, &mut state, false);
+
+            let mut previous_knot = state.head();
+            for &knot in state.tail() {
+               prop_assert!((knot.x - previous_knot.x).abs() <= 1);
+               prop_assert!((knot.y - previous_knot.y).abs() <= 1);
+               previous_knot = knot;
+            }
+         }
+      }
+
+      #[test]
+      fn matches_the_reference_simulation(
+         motions in prop::collection::vec(motion(), 0..50),
+         rope_size in 2..12usize,
+      ) {
+         let mut state = SimulationState::new(rope_size);
+         for &motion in &motions {
+            simulate(motion, &mut state, false);
+         }
+
+         let visited = state
+            .visited()
+            .iter()
+            .map(|point| (point.x, point.y))
+            .collect::<HashSet<_>>();
+
+         prop_assert_eq!(visited, reference_visited(&motions, rope_size));
+      }
+   }
 }
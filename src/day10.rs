@@ -1,12 +1,16 @@
+//! Day 10: Cathode-Ray Tube
+
 use std::{collections::VecDeque, fmt::Display};
 
+use crate::{error::ParseError, solution::Solution};
+
 #[derive(Debug, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
    AddX(i32),
    NoOp,
 }
 
-type Program = VecDeque<Instruction>;
+pub type Program = VecDeque<Instruction>;
 
 #[derive(Debug, Clone, Copy)]
 struct ExecutingInstruction {
@@ -29,16 +33,18 @@ impl From<Instruction> for ExecutingInstruction {
    }
 }
 
-struct CPU {
+/// A CPU with a single `X` register, which records the signal strength
+/// during the 20th cycle and every 40 cycles after that.
+pub struct CPU {
    program: Option<Program>,
    queue: Option<ExecutingInstruction>,
    current_cycle: i32,
-   signal_strengths: Vec<i32>,
+   signal_strengths: Vec<i64>,
    x_register: i32,
 }
 
 impl CPU {
-   fn new() -> Self {
+   pub fn new() -> Self {
       CPU {
          program: None,
          queue: None,
@@ -48,11 +54,23 @@ impl CPU {
       }
    }
 
-   fn load_program(&mut self, program: &Program) {
+   pub fn current_cycle(&self) -> i32 {
+      self.current_cycle
+   }
+
+   pub fn x_register(&self) -> i32 {
+      self.x_register
+   }
+
+   pub fn signal_strengths(&self) -> &[i64] {
+      &self.signal_strengths
+   }
+
+   pub fn load_program(&mut self, program: &Program) {
       self.program = Some(program.clone());
    }
 
-   fn execute_cycle(&mut self, debug: bool) {
+   pub fn execute_cycle(&mut self, debug: bool) {
       match self.program {
          None => panic!("No program loaded"),
 
@@ -60,7 +78,7 @@ impl CPU {
             self.current_cycle += 1;
 
             if (self.current_cycle - 20) % 40 == 0 {
-               let signal_strength = self.x_register * self.current_cycle;
+               let signal_strength = self.x_register as i64 * self.current_cycle as i64;
                self.signal_strengths.push(signal_strength);
             }
 
@@ -104,7 +122,7 @@ impl CPU {
       }
    }
 
-   fn is_finished(&self) -> bool {
+   pub fn is_finished(&self) -> bool {
       match self.program {
          None => panic!("No program loaded"),
          Some(ref program) => program.is_empty(),
@@ -112,28 +130,45 @@ impl CPU {
    }
 }
 
-struct CRT {
+impl Default for CPU {
+   fn default() -> Self {
+      Self::new()
+   }
+}
+
+/// A 40x6 screen, drawn one pixel per CPU cycle. Cycles past the last pixel
+/// draw nothing.
+pub struct CRT {
    pixels: [[bool; 40]; 6],
 }
 
 impl CRT {
-   fn new() -> Self {
+   pub fn new() -> Self {
       CRT {
          pixels: [[false; 40]; 6],
       }
    }
 
-   fn draw_pixel(&mut self, cycle: i32, sprite_position: i32) {
+   pub fn draw_pixel(&mut self, cycle: i32, sprite_position: i32) {
       let y = (cycle / 40) as usize;
       let x = (cycle % 40) as usize;
 
-      let sprite_cursor = sprite_position - 1..=sprite_position + 1;
-      if sprite_cursor.contains(&(x as i32)) {
-         self.pixels[y][x] = true;
+      let Some(row) = self.pixels.get_mut(y) else {
+         return;
+      };
+
+      if (x as i64 - sprite_position as i64).abs() <= 1 {
+         row[x] = true;
       }
    }
 }
 
+impl Default for CRT {
+   fn default() -> Self {
+      Self::new()
+   }
+}
+
 impl Display for CRT {
    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
       for row in self.pixels.iter() {
@@ -147,45 +182,274 @@ impl Display for CRT {
    }
 }
 
-fn parse_input() -> Program {
-   include_str!("../input/day10.txt")
-      .lines()
-      .map(|line| {
-         let tokens = line.split(" ").collect::<Vec<_>>();
-         let instruction = tokens.get(0);
-         let value = tokens.get(1).and_then(|token| token.parse::<i32>().ok());
-
-         match instruction {
-            Some(&"addx") => Instruction::AddX(value.unwrap()),
-            Some(&"noop") => Instruction::NoOp,
-            _ => unreachable!(),
-         }
-      })
-      .collect()
+fn parse_instruction(line_number: usize, line: &str) -> Result<Instruction, ParseError> {
+   let error =
+      |text: &str, message: &str| ParseError::new(Day10::DAY, line_number, line, text, message);
+
+   let tokens = line.split(' ').collect::<Vec<_>>();
+
+   match tokens[..] {
+      ["noop"] => Ok(Instruction::NoOp),
+      ["addx", value] => value
+         .parse::<i32>()
+         .map(Instruction::AddX)
+         .map_err(|_| error(value, "expected an integer")),
+      ["addx"] => Err(error(&line[line.len()..], "expected a value after `addx`")),
+      _ => Err(error(tokens[0], "expected `addx <value>` or `noop`")),
+   }
+}
+
+fn parse_input(input: &str) -> Result<Program, ParseError> {
+   let mut program = Program::new();
+   let mut x_register = 1i32;
+
+   for (index, line) in input.lines().enumerate() {
+      let instruction = parse_instruction(index + 1, line)?;
+
+      // Keep the X register in range, so that the program can be run.
+      if let Instruction::AddX(value) = instruction {
+         x_register = x_register.checked_add(value).ok_or_else(|| {
+            ParseError::new(
+               Day10::DAY,
+               index + 1,
+               line,
+               line.split(' ').nth(1).unwrap_or(line),
+               "this takes the X register out of range",
+            )
+         })?;
+      }
+
+      program.push_back(instruction);
+   }
+
+   Ok(program)
 }
 
-pub fn solve_part_one() -> i32 {
-   let program = parse_input();
-   let mut cpu = CPU::new();
+pub struct Day10;
+
+impl Solution for Day10 {
+   const DAY: u8 = 10;
+
+   type Input = Program;
+   type PartOne = i64;
+   type PartTwo = String;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError> {
+      parse_input(input)
+   }
+
+   fn part_one(program: &Self::Input) -> i64 {
+      let mut cpu = CPU::new();
+
+      cpu.load_program(program);
+      while !cpu.is_finished() {
+         cpu.execute_cycle(false);
+      }
 
-   cpu.load_program(&program);
-   while !cpu.is_finished() {
-      cpu.execute_cycle(false);
+      cpu.signal_strengths.iter().sum()
    }
 
-   cpu.signal_strengths.iter().sum()
+   fn part_two(program: &Self::Input) -> String {
+      let mut cpu = CPU::new();
+      let mut crt = CRT::new();
+
+      cpu.load_program(program);
+      while !cpu.is_finished() {
+         crt.draw_pixel(cpu.current_cycle, cpu.x_register);
+         cpu.execute_cycle(false);
+      }
+
+      crt.to_string()
+   }
+
+   fn visualize(program: &Self::Input) -> Option<String> {
+      Some(Self::part_two(program))
+   }
 }
 
-pub fn solve_part_two() -> String {
-   let program = parse_input();
-   let mut cpu = CPU::new();
-   let mut crt = CRT::new();
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   const EXAMPLE: &str = "\
+addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+   #[test]
+   fn part_one_example() {
+      let program = Day10::parse(EXAMPLE).unwrap();
+      assert_eq!(Day10::part_one(&program), 13140);
+   }
 
-   cpu.load_program(&program);
-   while !cpu.is_finished() {
-      crt.draw_pixel(cpu.current_cycle, cpu.x_register);
-      cpu.execute_cycle(false);
+   #[test]
+   fn part_two_example() {
+      let program = Day10::parse(EXAMPLE).unwrap();
+      assert_eq!(
+         Day10::part_two(&program),
+         "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....
+"
+      );
    }
 
-   crt.to_string()
+   #[test]
+   fn ignores_cycles_past_the_end_of_the_screen() {
+      let program = Day10::parse(&"noop\n".repeat(300)).unwrap();
+      assert_eq!(Day10::part_two(&program).lines().count(), 6);
+   }
+
+   #[test]
+   fn rejects_programs_that_overflow_the_x_register() {
+      let error = Day10::parse("addx 2147483646\naddx 1").unwrap_err();
+      assert_eq!((error.line, error.column), (2, 6));
+   }
 }
@@ -0,0 +1,58 @@
+use std::{error::Error, fmt};
+
+/// An error found while parsing a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+   pub day: u8,
+   /// The 1-based line number of the offending text.
+   pub line: usize,
+   /// The 1-based column (in characters) where the offending text starts.
+   pub column: usize,
+   pub text: String,
+   pub message: String,
+}
+
+impl ParseError {
+   /// Creates an error pointing at `text` within `line`.
+   ///
+   /// `text` should be a slice of `line` so that its column can be worked out.
+   /// If it isn't (e.g. the error is about something missing), the error
+   /// points at the end of the line instead.
+   pub fn new(
+      day: u8,
+      line_number: usize,
+      line: &str,
+      text: &str,
+      message: impl Into<String>,
+   ) -> Self {
+      let line_start = line.as_ptr() as usize;
+      let text_start = text.as_ptr() as usize;
+
+      let offset = if text_start >= line_start && text_start + text.len() <= line_start + line.len()
+      {
+         text_start - line_start
+      } else {
+         line.len()
+      };
+
+      ParseError {
+         day,
+         line: line_number,
+         column: line[..offset].chars().count() + 1,
+         text: text.to_owned(),
+         message: message.into(),
+      }
+   }
+}
+
+impl fmt::Display for ParseError {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(
+         f,
+         "day {}, line {}, column {}: {}",
+         self.day, self.line, self.column, self.message
+      )
+   }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,40 @@
+//! Downloads puzzle inputs into `input/`.
+
+use std::{fs, path::Path};
+
+use crate::{
+   client::{Client, Config, CONFIG_PATH},
+   input::default_input_path,
+};
+
+/// Downloads the input for a day and saves it to `path`. An existing input is
+/// left alone, without making a request, unless `force` is set.
+pub fn fetch(client: &Client, day: u8, path: &Path, force: bool) -> Result<(), String> {
+   if path.exists() && !force {
+      return Err(format!(
+         "{} already exists (use --force to overwrite it)",
+         path.display()
+      ));
+   }
+
+   let input = client.get(&format!("/2022/day/{}/input", day))?;
+
+   if let Some(parent) = path.parent() {
+      fs::create_dir_all(parent)
+         .map_err(|error| format!("could not create {}: {}", parent.display(), error))?;
+   }
+
+   fs::write(path, input).map_err(|error| format!("could not write {}: {}", path.display(), error))
+}
+
+/// Downloads a day's input to `input/dayNN.txt`, with the settings from
+/// `AOC_SESSION` or `aoc.toml`.
+pub fn fetch_day(day: u8, force: bool) -> Result<(), String> {
+   let client = Client::new(Config::load(Path::new(CONFIG_PATH))?);
+   let path = default_input_path(day);
+
+   fetch(&client, day, &path, force)?;
+   println!("Saved the day {} input to {}", day, path.display());
+
+   Ok(())
+}
@@ -0,0 +1,376 @@
+//! Generates valid puzzle inputs of any size, for stress testing.
+//!
+//! The same day, size and seed always produce the same input.
+
+use std::{
+   fmt::Write as _,
+   fs,
+   io::{self, Write as _},
+   path::Path,
+};
+
+/// A small, fast pseudo-random number generator (SplitMix64). It is not
+/// suitable for anything but generating test data.
+pub struct Rng(u64);
+
+impl Rng {
+   pub fn new(seed: u64) -> Self {
+      Rng(seed)
+   }
+
+   pub fn next_u64(&mut self) -> u64 {
+      self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+      let mut z = self.0;
+      z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+      z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+      z ^ (z >> 31)
+   }
+
+   /// A number in `low..=high`.
+   pub fn range(&mut self, low: i64, high: i64) -> i64 {
+      let span = (high - low) as u64 + 1;
+      low + (self.next_u64() % span) as i64
+   }
+
+   /// An index in `0..len`.
+   pub fn index(&mut self, len: usize) -> usize {
+      (self.next_u64() % len as u64) as usize
+   }
+
+   /// Whether an event with the given probability happened.
+   pub fn chance(&mut self, probability: f64) -> bool {
+      ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+   }
+
+   pub fn choose<T: Copy>(&mut self, items: &[T]) -> T {
+      items[self.index(items.len())]
+   }
+
+   pub fn shuffle<T>(&mut self, items: &mut [T]) {
+      for i in (1..items.len()).rev() {
+         items.swap(i, self.index(i + 1));
+      }
+   }
+}
+
+/// Generates an input for a day. What `size` measures depends on the day,
+/// e.g. the number of elves on day 1 or the width of the grid on day 8.
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<String, String> {
+   let mut rng = Rng::new(seed);
+   let size = size.max(1);
+
+   Ok(match day {
+      1 => day01(&mut rng, size),
+      2 => day02(&mut rng, size),
+      3 => day03(&mut rng, size),
+      4 => day04(&mut rng, size),
+      5 => day05(&mut rng, size),
+      6 => day06(&mut rng, size),
+      7 => day07(&mut rng, size),
+      8 => day08(&mut rng, size),
+      9 => day09(&mut rng, size),
+      10 => day10(&mut rng, size),
+      _ => return Err(format!("there is no generator for day {}", day)),
+   })
+}
+
+/// Generates an input and writes it to `output`, or to stdout.
+pub fn write(day: u8, size: usize, seed: u64, output: Option<&Path>) -> Result<(), String> {
+   let input = generate(day, size, seed)?;
+
+   match output {
+      Some(path) => fs::write(path, input)
+         .map_err(|error| format!("could not write {}: {}", path.display(), error)),
+      None => io::stdout()
+         .write_all(input.as_bytes())
+         .map_err(|error| format!("could not write the input: {}", error)),
+   }
+}
+
+/// `size` elves, each carrying a few snacks.
+fn day01(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+
+   for elf in 0..size {
+      if elf > 0 {
+         input.push('\n');
+      }
+
+      for _ in 0..rng.range(1, 8) {
+         writeln!(input, "{}", rng.range(1000, 60000)).unwrap();
+      }
+   }
+
+   input
+}
+
+/// `size` rounds of rock paper scissors.
+fn day02(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+
+   for _ in 0..size {
+      let opponent = rng.choose(&['A', 'B', 'C']);
+      let player = rng.choose(&['X', 'Y', 'Z']);
+      writeln!(input, "{} {}", opponent, player).unwrap();
+   }
+
+   input
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Each rucksack has
+/// exactly one item in both compartments, and each group exactly one badge.
+fn day03(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+   let items = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+
+   for _ in 0..size.div_ceil(3) {
+      let mut items = items.clone();
+      rng.shuffle(&mut items);
+
+      // Every elf in the group draws from their own 17 items, plus the badge.
+      let badge = items[0];
+      for pool in items[1..].chunks(17) {
+         let common = rng.choose(pool);
+         let others = pool
+            .iter()
+            .filter(|&&item| item != common)
+            .collect::<Vec<_>>();
+         let (first_pool, second_pool) = others.split_at(others.len() / 2);
+
+         let half = rng.range(2, 16) as usize;
+         let mut first = vec![common, badge];
+         let mut second = vec![common];
+         first.extend((2..half).map(|_| *rng.choose(first_pool)));
+         second.extend((1..half).map(|_| *rng.choose(second_pool)));
+         rng.shuffle(&mut first);
+         rng.shuffle(&mut second);
+
+         input.extend(first);
+         input.extend(second);
+         input.push('\n');
+      }
+   }
+
+   input
+}
+
+/// `size` pairs of section assignments.
+fn day04(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+
+   for _ in 0..size {
+      let mut assignment = || {
+         let (a, b) = (rng.range(1, 99), rng.range(1, 99));
+         (a.min(b), a.max(b))
+      };
+
+      let (first, second) = (assignment(), assignment());
+      writeln!(input, "{}-{},{}-{}", first.0, first.1, second.0, second.1).unwrap();
+   }
+
+   input
+}
+
+/// Nine stacks of crates and `size` moves. No move ever empties a stack, so
+/// every stack has a crate on top at the end.
+fn day05(rng: &mut Rng, size: usize) -> String {
+   let max_height = 8 + size / 1000;
+   let mut heights = (0..9)
+      .map(|_| rng.range(1, max_height as i64) as usize)
+      .collect::<Vec<_>>();
+
+   // With more crates than stacks, some stack can always give one up.
+   heights[0] = heights[0].max(2);
+
+   let letters = ('A'..='Z').collect::<Vec<_>>();
+   let mut input = String::new();
+
+   for row in (0..*heights.iter().max().unwrap()).rev() {
+      let line = heights
+         .iter()
+         .map(|&height| {
+            if height > row {
+               format!("[{}]", rng.choose(&letters))
+            } else {
+               "   ".to_owned()
+            }
+         })
+         .collect::<Vec<_>>()
+         .join(" ");
+
+      writeln!(input, "{}", line).unwrap();
+   }
+
+   let axis = (1..=9)
+      .map(|stack| format!(" {} ", stack))
+      .collect::<Vec<_>>();
+   writeln!(input, "{}\n", axis.join(" ")).unwrap();
+
+   for _ in 0..size {
+      let from = loop {
+         let stack = rng.index(9);
+         if heights[stack] >= 2 {
+            break stack;
+         }
+      };
+
+      let to = (from + 1 + rng.index(8)) % 9;
+      let count = rng.range(1, heights[from] as i64 - 1) as usize;
+      heights[from] -= count;
+      heights[to] += count;
+
+      writeln!(input, "move {} from {} to {}", count, from + 1, to + 1).unwrap();
+   }
+
+   input
+}
+
+/// A buffer of `size` characters whose only markers are at the very end.
+fn day06(rng: &mut Rng, size: usize) -> String {
+   // Three letters can never make a four character marker.
+   let mut input = (0..size.saturating_sub(14))
+      .map(|_| rng.choose(&['a', 'b', 'c']))
+      .collect::<String>();
+
+   let mut marker = ('a'..='z').collect::<Vec<_>>();
+   rng.shuffle(&mut marker);
+   input.extend(&marker[..14]);
+   input.push('\n');
+
+   input
+}
+
+/// A shell session exploring `size` directories. The tree is built by a
+/// random walk, so it gets deeper as it grows.
+fn day07(rng: &mut Rng, size: usize) -> String {
+   let mut parents: Vec<Option<usize>> = vec![None];
+   let mut children: Vec<Vec<usize>> = vec![vec![]];
+   let mut current = 0;
+
+   while parents.len() < size {
+      match parents[current] {
+         Some(parent) if rng.chance(0.5) => current = parent,
+         _ => {
+            let directory = parents.len();
+            parents.push(Some(current));
+            children.push(vec![]);
+            children[current].push(directory);
+            current = directory;
+         }
+      }
+   }
+
+   let mut input = String::from("$ cd /\n");
+
+   // Visit the directories depth first, without recursing, since the tree
+   // can be very deep.
+   let mut stack = vec![(0, 0)];
+   while let Some((directory, next_child)) = stack.pop() {
+      if next_child == 0 {
+         input += "$ ls\n";
+
+         // The root holds a large file so that the disk is full enough for
+         // part two to have something to delete.
+         if directory == 0 {
+            input += "45000000 padding.bin\n";
+         }
+
+         for file in 0..rng.range(0, 3) {
+            writeln!(input, "{} f{}.txt", rng.range(1, 300000), file).unwrap();
+         }
+
+         for child in &children[directory] {
+            writeln!(input, "dir d{}", child).unwrap();
+         }
+      }
+
+      match children[directory].get(next_child) {
+         Some(&child) => {
+            writeln!(input, "$ cd d{}", child).unwrap();
+            stack.push((directory, next_child + 1));
+            stack.push((child, 0));
+         }
+         None if directory != 0 => input += "$ cd ..\n",
+         None => (),
+      }
+   }
+
+   input
+}
+
+/// A `size` × `size` patch of trees.
+fn day08(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::with_capacity(size * (size + 1));
+
+   for _ in 0..size {
+      for _ in 0..size {
+         input.push(char::from(b'0' + rng.range(0, 9) as u8));
+      }
+      input.push('\n');
+   }
+
+   input
+}
+
+/// `size` motions of the head of the rope.
+fn day09(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+
+   for _ in 0..size {
+      let direction = rng.choose(&['U', 'D', 'L', 'R']);
+      writeln!(input, "{} {}", direction, rng.range(1, 20)).unwrap();
+   }
+
+   input
+}
+
+/// A program that runs for at most `size` cycles, keeping the sprite near
+/// the screen.
+fn day10(rng: &mut Rng, size: usize) -> String {
+   let mut input = String::new();
+   let mut cycles = 0;
+   let mut x = 1;
+
+   while cycles < size {
+      if cycles + 2 <= size && rng.chance(0.6) {
+         let value = rng.range((-x - 1).max(-20), (41 - x).min(20));
+         x += value;
+         cycles += 2;
+         writeln!(input, "addx {}", value).unwrap();
+      } else {
+         cycles += 1;
+         input += "noop\n";
+      }
+   }
+
+   input
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+   use crate::{registry::SOLUTIONS, solution::Part};
+
+   #[test]
+   fn every_day_generates_solvable_inputs() {
+      for solution in SOLUTIONS {
+         let day = solution.day();
+
+         // Newly scaffolded days don't have a generator yet.
+         let input = match generate(day, 200, 7) {
+            Ok(input) => input,
+            Err(error) => {
+               assert_eq!(error, format!("there is no generator for day {}", day));
+               continue;
+            }
+         };
+         assert_eq!(input, generate(day, 200, 7).unwrap());
+         assert_ne!(input, generate(day, 200, 8).unwrap());
+
+         let parsed = solution.parse(&input).unwrap();
+         for part in Part::ALL {
+            parsed.solve(part);
+         }
+      }
+   }
+}
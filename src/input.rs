@@ -0,0 +1,34 @@
+use std::{
+   fs::{self, File},
+   io::{self, BufRead, BufReader, Read},
+   path::{Path, PathBuf},
+};
+
+/// Returns the default location of the puzzle input for a day.
+pub fn default_input_path(day: u8) -> PathBuf {
+   PathBuf::from(format!("input/day{:02}.txt", day))
+}
+
+/// Reads the puzzle input from a file, or from stdin if the path is `-`.
+pub fn read_input(path: &Path) -> Result<String, String> {
+   let result = if path == Path::new("-") {
+      let mut input = String::new();
+      io::stdin().read_to_string(&mut input).map(|_| input)
+   } else {
+      fs::read_to_string(path)
+   };
+
+   result.map_err(|error| format!("could not read {}: {}", path.display(), error))
+}
+
+/// Opens the puzzle input for reading a line at a time, from a file or from
+/// stdin if the path is `-`, for inputs too large to read all at once.
+pub fn open_input(path: &Path) -> Result<Box<dyn BufRead>, String> {
+   if path == Path::new("-") {
+      return Ok(Box::new(io::stdin().lock()));
+   }
+
+   File::open(path)
+      .map(|file| Box::new(BufReader::new(file)) as Box<dyn BufRead>)
+      .map_err(|error| format!("could not read {}: {}", path.display(), error))
+}
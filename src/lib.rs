@@ -0,0 +1,41 @@
+//! Solutions to [Advent of Code 2022](https://adventofcode.com/2022).
+//!
+//! Each day lives in its own module (`day01` to `day10`) and exposes the
+//! types and functions used to solve it alongside a unit struct implementing
+//! [`solution::Solution`]. The [`registry`] lists every solved day, and the
+//! [`runner`] solves a selection of them the way the command line does.
+//!
+//! ```no_run
+//! use advent_of_code_2022::{day01::Day01, solution::Solution};
+//!
+//! let input = std::fs::read_to_string("input/day01.txt").unwrap();
+//! let food_bags = Day01::parse(&input).unwrap();
+//! println!("{}", Day01::part_one(&food_bags));
+//! ```
+
+pub mod analysis;
+pub mod answers;
+pub mod client;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod input;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod timing;
+pub mod tui;
+pub mod watch;
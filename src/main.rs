@@ -1,64 +1,239 @@
-#![feature(exclusive_range_pattern)]
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-
-fn main() {
-   println!("=== Day 1 ===");
-   println!("Part 1: {:?}", day01::solve_part_one());
-   println!("Part 2: {:?}", day01::solve_part_two());
-   println!();
-
-   println!("=== Day 2 ===");
-   println!("Part 1: {:?}", day02::solve_part_one());
-   println!("Part 2: {:?}", day02::solve_part_two());
-   println!();
-
-   println!("=== Day 3 ===");
-   println!("Part 1: {:?}", day03::solve_part_one());
-   println!("Part 2: {:?}", day03::solve_part_two());
-   println!();
-
-   println!("=== Day 4 ===");
-   println!("Part 1: {:?}", day04::solve_part_one());
-   println!("Part 2: {:?}", day04::solve_part_two());
-   println!();
-
-   println!("=== Day 5 ===");
-   println!("Part 1: {:?}", day05::solve_part_one());
-   println!("Part 2: {:?}", day05::solve_part_two());
-   println!();
-
-   println!("=== Day 6 ===");
-   println!("Part 1: {:?}", day06::solve_part_one());
-   println!("Part 2: {:?}", day06::solve_part_two());
-   println!();
-
-   println!("=== Day 7 ===");
-   println!("Part 1: {:?}", day07::solve_part_one());
-   println!("Part 2: {:?}", day07::solve_part_two());
-   println!();
-
-   println!("=== Day 8 ===");
-   println!("Part 1: {:?}", day08::solve_part_one());
-   println!("Part 2: {:?}", day08::solve_part_two());
-   println!();
-
-   println!("=== Day 9 ===");
-   println!("Part 1: {:?}", day09::solve_part_one());
-   println!("Part 2: {:?}", day09::solve_part_two());
-   println!();
-
-   println!("=== Day 10 ===");
-   println!("Part 1: {}", day10::solve_part_one());
-   println!("Part 2: <See CRT Output>\n\n{}", day10::solve_part_two());
-   println!();
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand};
+
+use advent_of_code_2022::{
+   analysis, fetch, generate,
+   runner::{self, DaySelection, Format, RunOptions},
+   scaffold,
+   solution::Part,
+   submit, tui, watch,
+};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2022 solutions")]
+struct Cli {
+   #[command(subcommand)]
+   command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+   /// Solve the selected days (all of them when no subcommand is given).
+   Run(RunArgs),
+
+   /// Re-solve a day whenever `src/dayNN.rs` or `input/dayNN.txt` changes,
+   /// comparing the answers with the previous ones.
+   Watch {
+      /// The day to watch.
+      day: u8,
+   },
+
+   /// Download a day's input to `input/dayNN.txt`, using the session token
+   /// from `AOC_SESSION` or `aoc.toml`.
+   Fetch {
+      /// The day to download.
+      #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+      day: u8,
+
+      /// Replace the input if it was already downloaded.
+      #[arg(long)]
+      force: bool,
+   },
+
+   /// Solve a part and submit the answer, unless it is already known to be
+   /// wrong from the guesses in `guesses.toml`.
+   Submit {
+      /// The day to submit.
+      day: u8,
+
+      /// The part to submit.
+      #[arg(value_parser = parse_part)]
+      part: Part,
+
+      /// Submit this answer instead of solving the part, e.g. for answers
+      /// that have to be read off a picture.
+      #[arg(long)]
+      answer: Option<String>,
+   },
+
+   /// Create `src/dayNN.rs` from a template and an empty `input/dayNN.txt`,
+   /// and register the day with the runner.
+   New {
+      /// The day to create.
+      #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+      day: u8,
+
+      /// The puzzle title, for the module documentation.
+      #[arg(long)]
+      title: Option<String>,
+   },
+
+   /// Describe a day's input with statistics. Only day 1 is supported: the
+   /// count, mean, median, percentiles, standard deviation and histogram of
+   /// the calories carried by each elf. The input is read a line at a time,
+   /// keeping only each elf's totals in memory.
+   Stats {
+      /// The day to describe.
+      day: u8,
+
+      /// Read the puzzle input from this file instead of `input/dayNN.txt`
+      /// (use `-` for stdin).
+      #[arg(long)]
+      input: Option<PathBuf>,
+
+      /// How many buckets to split the histogram into.
+      #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+      buckets: u64,
+
+      /// Instead of the statistics, list the K elves carrying the most
+      /// calories and their indices (from 0). Only K elves are kept in memory,
+      /// however large the input.
+      #[arg(long, value_name = "K")]
+      top: Option<usize>,
+
+      /// How to print the statistics (text or json).
+      #[arg(long, value_enum, default_value_t = Format::Text)]
+      format: Format,
+   },
+
+   /// Score the day 2 strategy guide under every reading of X, Y and Z: the
+   /// 6 ways to map them to shapes and the 6 ways to map them to outcomes.
+   Decode {
+      /// Read the puzzle input from this file instead of `input/day02.txt`
+      /// (use `-` for stdin).
+      #[arg(long)]
+      input: Option<PathBuf>,
+
+      /// List the readings that give this total score.
+      #[arg(long)]
+      observed: Option<u32>,
+   },
+
+   /// Browse the days, their answers, inputs, parsed structures and
+   /// visualizations in an interactive dashboard.
+   Tui,
+
+   /// Generate a valid input of any size for stress testing. The same day,
+   /// size and seed always give the same input.
+   Generate {
+      /// The day to generate an input for.
+      day: u8,
+
+      /// How big to make the input, e.g. the number of elves on day 1, the
+      /// width of the grid on day 8 or the number of motions on day 9.
+      #[arg(long, default_value_t = 1000)]
+      size: usize,
+
+      /// The seed for the random number generator.
+      #[arg(long, default_value_t = 0)]
+      seed: u64,
+
+      /// Write the input to this file instead of stdout.
+      #[arg(long)]
+      output: Option<PathBuf>,
+   },
+}
+
+#[derive(Args)]
+struct RunArgs {
+   /// Days to solve, e.g. `7`, `3..=6`, `3..6` or `1,4,9`.
+   #[arg(required_unless_present = "all", conflicts_with = "all")]
+   days: Option<DaySelection>,
+
+   /// Solve every day.
+   #[arg(long)]
+   all: bool,
+
+   /// Only solve the given part.
+   #[arg(long, value_parser = parse_part)]
+   part: Option<Part>,
+
+   /// Read the puzzle input from this file instead of `input/dayNN.txt`
+   /// (use `-` for stdin). Only valid when a single day is selected.
+   #[arg(long)]
+   input: Option<PathBuf>,
+
+   /// Report how long parsing and each part took.
+   #[arg(long)]
+   time: bool,
+
+   /// Solve each day this many times and report the min/median/max timings.
+   #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+   repeat: Option<u32>,
+
+   /// Compare each answer against `answers.toml` and fail on any mismatch.
+   #[arg(long, conflicts_with = "input")]
+   check: bool,
+
+   /// How to print the answers.
+   #[arg(long, value_enum, default_value_t = Format::Text)]
+   format: Format,
+
+   /// Solve the days, and both parts of each day, concurrently on a thread
+   /// pool. Reports the wall-clock time alongside the summed time of every
+   /// task.
+   #[arg(long)]
+   parallel: bool,
+}
+
+impl From<RunArgs> for RunOptions {
+   fn from(args: RunArgs) -> Self {
+      RunOptions {
+         days: args.days.unwrap_or_else(DaySelection::all),
+         parts: match args.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+         },
+         input: args.input,
+         repeat: args.repeat.or(if args.time { Some(1) } else { None }),
+         check: args.check,
+         format: args.format,
+         parallel: args.parallel,
+      }
+   }
+}
+
+fn parse_part(part: &str) -> Result<Part, String> {
+   match part {
+      "1" => Ok(Part::One),
+      "2" => Ok(Part::Two),
+      _ => Err("expected 1 or 2".to_owned()),
+   }
+}
+
+fn main() -> ExitCode {
+   let cli = Cli::parse();
+
+   let result = match cli.command {
+      None => runner::run(&RunOptions::default()),
+      Some(Command::Run(args)) => runner::run(&args.into()),
+      Some(Command::Watch { day }) => watch::watch(day),
+      Some(Command::Fetch { day, force }) => fetch::fetch_day(day, force),
+      Some(Command::Submit { day, part, answer }) => submit::submit_answer(day, part, answer),
+      Some(Command::New { day, title }) => scaffold::new_day(day, title.as_deref()),
+      Some(Command::Stats {
+         day,
+         input,
+         buckets,
+         top,
+         format,
+      }) => analysis::stats(day, input.as_deref(), buckets as usize, top, format),
+      Some(Command::Decode { input, observed }) => analysis::decode(input.as_deref(), observed),
+      Some(Command::Tui) => tui::run(),
+      Some(Command::Generate {
+         day,
+         size,
+         seed,
+         output,
+      }) => generate::write(day, size, seed, output.as_deref()),
+   };
+
+   match result {
+      Ok(()) => ExitCode::SUCCESS,
+      Err(message) => {
+         eprintln!("error: {}", message);
+         ExitCode::FAILURE
+      }
+   }
 }
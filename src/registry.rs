@@ -0,0 +1,23 @@
+use crate::solution::DynSolution;
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10};
+
+/// Every solved day, in order.
+pub const SOLUTIONS: &[&dyn DynSolution] = &[
+   &day01::Day01,
+   &day02::Day02,
+   &day03::Day03,
+   &day04::Day04,
+   &day05::Day05,
+   &day06::Day06,
+   &day07::Day07,
+   &day08::Day08,
+   &day09::Day09,
+   &day10::Day10,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+   SOLUTIONS
+      .iter()
+      .copied()
+      .find(|solution| solution.day() == day)
+}
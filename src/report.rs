@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+/// One answer, in the shape emitted by the machine-readable output formats.
+#[derive(Debug, Serialize)]
+pub struct Record {
+   pub day: u8,
+   pub part: u8,
+   pub answer: String,
+   #[serde(rename = "type")]
+   pub answer_type: &'static str,
+   /// The median time taken to solve the part, in nanoseconds.
+   pub elapsed_ns: u64,
+   /// How the answer compared to `answers.toml`, if it was checked.
+   #[serde(skip_serializing_if = "Option::is_none")]
+   pub check: Option<&'static str>,
+}
+
+pub fn to_json(records: &[Record]) -> String {
+   serde_json::to_string_pretty(records).expect("records are always serializable")
+}
+
+/// Formats the records as CSV, quoting fields where RFC 4180 requires it.
+pub fn to_csv(records: &[Record]) -> String {
+   let with_check = records.iter().any(|record| record.check.is_some());
+
+   let quote = |field: &str| {
+      if field.contains([',', '"', '\n', '\r']) {
+         format!("\"{}\"", field.replace('"', "\"\""))
+      } else {
+         field.to_owned()
+      }
+   };
+
+   let mut csv = String::from("day,part,answer,type,elapsed_ns");
+   if with_check {
+      csv += ",check";
+   }
+   csv += "\n";
+
+   for record in records {
+      csv += &format!(
+         "{},{},{},{},{}",
+         record.day,
+         record.part,
+         quote(&record.answer),
+         quote(record.answer_type),
+         record.elapsed_ns
+      );
+
+      if with_check {
+         csv += &format!(",{}", record.check.unwrap_or_default());
+      }
+
+      csv += "\n";
+   }
+
+   csv
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn csv_quotes_multi_line_answers() {
+      let records = [Record {
+         day: 10,
+         part: 2,
+         answer: "#.\n.#\n".to_owned(),
+         answer_type: "String",
+         elapsed_ns: 42,
+         check: None,
+      }];
+
+      assert_eq!(
+         to_csv(&records),
+         "day,part,answer,type,elapsed_ns\n10,2,\"#.\n.#\n\",String,42\n"
+      );
+   }
+}
@@ -0,0 +1,413 @@
+//! Solves a selection of days and prints their answers.
+
+use std::{
+   path::{Path, PathBuf},
+   str::FromStr,
+   time::Instant,
+};
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+
+use crate::{
+   answers::{Answers, Verdict, ANSWERS_PATH},
+   error::ParseError,
+   input::{default_input_path, read_input},
+   registry::{self, SOLUTIONS},
+   report::{self, Record},
+   solution::{Answer, DynSolution, Part},
+   timing::{self, format_duration, DayTimings, Samples},
+};
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+   /// Human-readable text.
+   Text,
+   /// A JSON array with one record per part.
+   Json,
+   /// CSV with one row per part.
+   Csv,
+}
+
+/// What to solve and how, once the command line has been resolved.
+pub struct RunOptions {
+   pub days: DaySelection,
+   pub parts: Vec<Part>,
+   pub input: Option<PathBuf>,
+   /// How many times to solve each day, or `None` if timings weren't requested.
+   pub repeat: Option<u32>,
+   /// Whether to compare the answers against `answers.toml`.
+   pub check: bool,
+   pub format: Format,
+   /// Whether to solve the days, and the parts of each day, concurrently.
+   pub parallel: bool,
+}
+
+impl Default for RunOptions {
+   fn default() -> Self {
+      RunOptions {
+         days: DaySelection::all(),
+         parts: Part::ALL.to_vec(),
+         input: None,
+         repeat: None,
+         check: false,
+         format: Format::Text,
+         parallel: false,
+      }
+   }
+}
+
+/// A list of days given on the command line, in the order they should run.
+#[derive(Clone, Debug)]
+pub struct DaySelection(pub Vec<u8>);
+
+impl DaySelection {
+   pub fn all() -> Self {
+      DaySelection(SOLUTIONS.iter().map(|solution| solution.day()).collect())
+   }
+}
+
+impl FromStr for DaySelection {
+   type Err = String;
+
+   fn from_str(input: &str) -> Result<Self, Self::Err> {
+      let parse_day = |day: &str| {
+         day.trim()
+            .parse::<u8>()
+            .map_err(|_| format!("`{}` is not a day number", day.trim()))
+      };
+
+      let mut days = vec![];
+
+      for item in input.split(',') {
+         if let Some((start, end)) = item.split_once("..=") {
+            days.extend(parse_day(start)?..=parse_day(end)?);
+         } else if let Some((start, end)) = item.split_once("..") {
+            days.extend(parse_day(start)?..parse_day(end)?);
+         } else {
+            days.push(parse_day(item)?);
+         }
+      }
+
+      if days.is_empty() {
+         return Err(format!("`{}` does not select any days", input));
+      }
+
+      Ok(DaySelection(days))
+   }
+}
+
+/// Looks up the solution to a day, listing the available days if there is
+/// none.
+pub fn find_solution(day: u8) -> Result<&'static dyn DynSolution, String> {
+   registry::find(day).ok_or_else(|| {
+      let available = DaySelection::all().0;
+      format!(
+         "day {} has no solution (available days: {}-{})",
+         day,
+         available.first().unwrap(),
+         available.last().unwrap()
+      )
+   })
+}
+
+/// Formats a parse error as a diagnostic that points at the offending text.
+pub fn format_parse_error(error: &ParseError, source: &Path, input: &str) -> String {
+   let line = input.lines().nth(error.line - 1).unwrap_or_default();
+   let line_number = error.line.to_string();
+   let gutter = " ".repeat(line_number.len());
+   let indent = " ".repeat(error.column - 1);
+   let underline = "^".repeat(error.text.chars().count().max(1));
+   let source = match source.to_str() {
+      Some("-") => "<stdin>".into(),
+      _ => source.display().to_string(),
+   };
+
+   format!(
+      "could not parse the day {} input: {}\n{} --> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+      error.day,
+      error.message,
+      gutter,
+      source,
+      error.line,
+      error.column,
+      gutter,
+      line_number,
+      line,
+      gutter,
+      indent,
+      underline
+   )
+}
+
+/// Prints an answer in the text format, along with its timing and check if
+/// they were requested.
+fn print_answer(
+   part: Part,
+   answer: &str,
+   samples: &Samples,
+   timed: bool,
+   verdict: Option<&Verdict>,
+) {
+   let mut notes = String::new();
+
+   if timed {
+      notes += &format!(" ({})", samples);
+   }
+
+   notes += &match verdict {
+      None => String::new(),
+      Some(Verdict::Pass) => " [pass]".to_owned(),
+      Some(Verdict::Unknown) => " [no expected answer]".to_owned(),
+      Some(Verdict::Fail { expected }) if expected.contains('\n') => {
+         format!(" [FAIL, expected:\n\n{}\n]", expected.trim_end())
+      }
+      Some(Verdict::Fail { expected }) => format!(" [FAIL, expected {}]", expected),
+   };
+
+   // Multi-line answers (such as the CRT output of day 10) are
+   // printed below the label so that they line up.
+   if answer.contains('\n') {
+      println!("Part {}{}:\n\n{}", part, notes, answer.trim_end());
+   } else {
+      println!("Part {}: {}{}", part, answer, notes);
+   }
+}
+
+/// The answers and timings for one day.
+struct SolvedDay {
+   timings: DayTimings,
+   answers: Vec<(Part, Answer)>,
+}
+
+/// Reads, parses and solves one day, repeating the work as many times as
+/// requested. The parts are solved concurrently in parallel mode.
+fn solve_day(solution: &dyn DynSolution, options: &RunOptions) -> Result<SolvedDay, String> {
+   let day = solution.day();
+   let path = match &options.input {
+      Some(path) => path.to_owned(),
+      None => default_input_path(day),
+   };
+
+   let input = read_input(&path)?;
+   let repeat = options.repeat.unwrap_or(1);
+   let mut timings = DayTimings::new(day);
+
+   let mut parsed = None;
+   for _ in 0..repeat {
+      let input = timings
+         .parse
+         .record(|| solution.parse(&input))
+         .map_err(|error| format_parse_error(&error, &path, &input))?;
+      parsed = Some(input);
+   }
+
+   let parsed = parsed.expect("every day is solved at least once");
+
+   let solve_part = |&part: &Part| {
+      let mut samples = Samples::default();
+      let mut answer = None;
+
+      for _ in 0..repeat {
+         answer = Some(samples.record(|| parsed.solve(part)));
+      }
+
+      (
+         part,
+         samples,
+         answer.expect("every part is solved at least once"),
+      )
+   };
+
+   let solved_parts: Vec<_> = if options.parallel {
+      options.parts.par_iter().map(solve_part).collect()
+   } else {
+      options.parts.iter().map(solve_part).collect()
+   };
+
+   let mut answers = vec![];
+   for (part, samples, answer) in solved_parts {
+      timings.parts.push((part, samples));
+      answers.push((part, answer));
+   }
+
+   Ok(SolvedDay { timings, answers })
+}
+
+/// Solves one part of a day using its default input.
+pub fn solve(day: u8, part: Part) -> Result<Answer, String> {
+   let options = RunOptions {
+      days: DaySelection(vec![day]),
+      parts: vec![part],
+      ..RunOptions::default()
+   };
+
+   let solved_day = solve_day(find_solution(day)?, &options)?;
+   let (_, answer) = solved_day
+      .answers
+      .into_iter()
+      .next()
+      .expect("the part was solved");
+
+   Ok(answer)
+}
+
+/// Solves the selected days, printing the answers in the requested format.
+/// A day whose input cannot be read or parsed does not stop the others: every
+/// day that was solved is printed first, then the errors are reported. Also
+/// fails if any answer did not match `answers.toml`.
+pub fn run(options: &RunOptions) -> Result<(), String> {
+   if options.input.is_some() && options.days.0.len() != 1 {
+      return Err("--input can only be used when a single day is selected".to_owned());
+   }
+
+   // Resolve every day up front so that a typo in the selection is
+   // reported before we spend any time solving the valid days.
+   let solutions = options
+      .days
+      .0
+      .iter()
+      .map(|&day| find_solution(day))
+      .collect::<Result<Vec<_>, _>>()?;
+
+   let answers = if options.check {
+      Answers::load(Path::new(ANSWERS_PATH))?
+   } else {
+      Answers::default()
+   };
+
+   let text = options.format == Format::Text;
+   let mut all_timings = vec![];
+   let mut records = vec![];
+   let mut failures = 0;
+   let mut errors = vec![];
+
+   let start = Instant::now();
+
+   // Days solved in parallel are only printed once they have all finished,
+   // whereas sequential days are printed as soon as each one is solved.
+   let solved_days: Box<dyn Iterator<Item = Result<SolvedDay, String>>> = if options.parallel {
+      let solved_days = solutions
+         .par_iter()
+         .map(|&solution| solve_day(solution, options))
+         .collect::<Vec<_>>();
+      Box::new(solved_days.into_iter())
+   } else {
+      Box::new(
+         solutions
+            .iter()
+            .map(|&solution| solve_day(solution, options)),
+      )
+   };
+
+   for solved_day in solved_days {
+      let SolvedDay {
+         timings,
+         answers: computed_answers,
+      } = match solved_day {
+         Ok(solved_day) => solved_day,
+         Err(error) => {
+            errors.push(error);
+            continue;
+         }
+      };
+      let day = timings.day;
+
+      if text {
+         println!("=== Day {} ===", day);
+
+         if options.repeat.is_some() {
+            println!("Parse: {}", timings.parse);
+         }
+      }
+
+      for (part, answer) in computed_answers {
+         let verdict = options
+            .check
+            .then(|| answers.check(day, part, &answer.value));
+
+         if let Some(Verdict::Fail { .. }) = verdict {
+            failures += 1;
+         }
+
+         let samples = timings.part(part).expect("every solved part is timed");
+
+         if text {
+            print_answer(
+               part,
+               &answer.value,
+               samples,
+               options.repeat.is_some(),
+               verdict.as_ref(),
+            );
+         }
+
+         records.push(Record {
+            day,
+            part: part.number(),
+            answer: answer.value,
+            answer_type: answer.type_name,
+            elapsed_ns: samples.median().as_nanos() as u64,
+            check: verdict.map(|verdict| verdict.as_str()),
+         });
+      }
+
+      if text {
+         println!();
+      }
+
+      all_timings.push(timings);
+   }
+
+   let wall_clock = start.elapsed();
+
+   match options.format {
+      Format::Text => {
+         if options.repeat.is_some() {
+            timing::print_summary(&all_timings);
+         }
+
+         if options.parallel {
+            if options.repeat.is_some() {
+               println!();
+            }
+
+            println!(
+               "Solved {} day(s) in {} wall-clock, {} summed task time",
+               all_timings.len(),
+               format_duration(wall_clock),
+               format_duration(all_timings.iter().map(DayTimings::task_time).sum())
+            );
+         }
+      }
+      Format::Json => println!("{}", report::to_json(&records)),
+      Format::Csv => print!("{}", report::to_csv(&records)),
+   }
+
+   // A single error is reported as it is; several are listed before a
+   // summary of what failed.
+   if errors.len() == 1 && failures == 0 {
+      return Err(errors.remove(0));
+   }
+
+   for error in &errors {
+      eprintln!("error: {}", error);
+   }
+
+   let mut problems = vec![];
+   if !errors.is_empty() {
+      problems.push(format!("{} day(s) could not be solved", errors.len()));
+   }
+   if failures > 0 {
+      problems.push(format!(
+         "{} answer(s) did not match {}",
+         failures, ANSWERS_PATH
+      ));
+   }
+
+   if problems.is_empty() {
+      Ok(())
+   } else {
+      Err(problems.join(", and "))
+   }
+}
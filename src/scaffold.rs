@@ -0,0 +1,203 @@
+//! Generates the files for a new day and registers it with the runner.
+
+use std::{
+   fs,
+   path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use crate::{input::default_input_path, watch::default_source_path};
+
+const LIB_PATH: &str = "src/lib.rs";
+const REGISTRY_PATH: &str = "src/registry.rs";
+
+/// The longest line rustfmt leaves alone.
+const MAX_WIDTH: usize = 100;
+
+/// Returns the source of a new day module, with a parser, stubs for both
+/// parts and example tests that are ignored until the example is filled in.
+pub fn template(day: u8, title: Option<&str>) -> String {
+   let heading = match title {
+      Some(title) => format!("Day {}: {}", day, title),
+      None => format!("Day {}", day),
+   };
+
+   include_str!("scaffold/day.rs.template")
+      .replace("{{heading}}", &heading)
+      .replace("{{day}}", &day.to_string())
+      .replace("{{NN}}", &format!("{:02}", day))
+}
+
+/// Adds `pub mod dayNN;` to the library root, keeping the days in order.
+pub fn register_module(lib: &str, day: u8) -> Result<String, String> {
+   let module = format!("day{:02}", day);
+   let declaration = Regex::new(r"(?m)^pub mod (day\d+);\n").unwrap();
+
+   let mut insert_at = None;
+   for captures in declaration.captures_iter(lib) {
+      let whole = captures.get(0).unwrap();
+      if captures[1] == module {
+         return Err(format!("{} already declares `{}`", LIB_PATH, module));
+      }
+
+      if captures[1] < *module.as_str() {
+         insert_at = Some(whole.end());
+      } else if insert_at.is_none() {
+         insert_at = Some(whole.start());
+      }
+   }
+
+   let insert_at = insert_at.ok_or_else(|| format!("{} declares no days", LIB_PATH))?;
+   Ok(format!(
+      "{}pub mod {};\n{}",
+      &lib[..insert_at],
+      module,
+      &lib[insert_at..]
+   ))
+}
+
+/// Adds the day to the imports and the list of solutions in the registry.
+pub fn register_solution(registry: &str, day: u8) -> Result<String, String> {
+   let module = format!("day{:02}", day);
+   let invalid = || format!("could not find the list of days in {}", REGISTRY_PATH);
+
+   let imports = Regex::new(r"use crate::\{(\s*day[^}]*)\};\n").unwrap();
+   let captures = imports.captures(registry).ok_or_else(invalid)?;
+
+   let mut days = captures[1]
+      .split(',')
+      .map(str::trim)
+      .filter(|module| !module.is_empty())
+      .map(str::to_owned)
+      .collect::<Vec<_>>();
+
+   if days.contains(&module) {
+      return Err(format!("{} already registers `{}`", REGISTRY_PATH, module));
+   }
+
+   days.push(module.clone());
+   days.sort();
+
+   let range = captures.get(0).unwrap().range();
+   let registry = format!(
+      "{}{}{}",
+      &registry[..range.start],
+      format_imports(&days),
+      &registry[range.end..]
+   );
+
+   let entry = Regex::new(r"(?m)^   &(day\d+)::Day\d+,\n").unwrap();
+   let insert_at = entry
+      .captures_iter(&registry)
+      .filter(|captures| captures[1] < *module.as_str())
+      .map(|captures| captures.get(0).unwrap().end())
+      .last()
+      .or_else(|| entry.find(&registry).map(|entry| entry.start()))
+      .ok_or_else(invalid)?;
+
+   Ok(format!(
+      "{}   &{}::Day{:02},\n{}",
+      &registry[..insert_at],
+      module,
+      day,
+      &registry[insert_at..]
+   ))
+}
+
+/// Formats the day imports the way rustfmt would.
+fn format_imports(days: &[String]) -> String {
+   let single_line = format!("use crate::{{{}}};\n", days.join(", "));
+   if single_line.len() <= MAX_WIDTH + 1 {
+      return single_line;
+   }
+
+   let mut lines = vec![];
+   let mut line = String::from("  ");
+
+   for day in days {
+      if line.len() + day.len() + 2 >= MAX_WIDTH {
+         lines.push(line);
+         line = String::from("  ");
+      }
+
+      line += &format!(" {},", day);
+   }
+
+   lines.push(line);
+   format!("use crate::{{\n{}\n}};\n", lines.join("\n"))
+}
+
+/// Creates `src/dayNN.rs` and an empty `input/dayNN.txt` under `root`, and
+/// registers the day. Returns the files that were created or changed.
+pub fn scaffold(root: &Path, day: u8, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+   let read = |path: &Path| {
+      fs::read_to_string(path)
+         .map_err(|error| format!("could not read {}: {}", path.display(), error))
+   };
+   let write = |path: &Path, contents: &str| {
+      fs::write(path, contents)
+         .map_err(|error| format!("could not write {}: {}", path.display(), error))
+   };
+
+   let source_path = root.join(default_source_path(day));
+   if source_path.exists() {
+      return Err(format!("{} already exists", source_path.display()));
+   }
+
+   // Check that the day can be registered before writing anything.
+   let lib_path = root.join(LIB_PATH);
+   let registry_path = root.join(REGISTRY_PATH);
+   let lib = register_module(&read(&lib_path)?, day)?;
+   let registry = register_solution(&read(&registry_path)?, day)?;
+
+   write(&source_path, &template(day, title))?;
+   write(&lib_path, &lib)?;
+   write(&registry_path, &registry)?;
+   let mut changed = vec![source_path, lib_path, registry_path];
+
+   // Keep an input that was already fetched.
+   let input_path = root.join(default_input_path(day));
+   if !input_path.exists() {
+      if let Some(parent) = input_path.parent() {
+         fs::create_dir_all(parent)
+            .map_err(|error| format!("could not create {}: {}", parent.display(), error))?;
+      }
+
+      write(&input_path, "")?;
+      changed.push(input_path);
+   }
+
+   Ok(changed)
+}
+
+/// Scaffolds a day in the current directory, listing the files written.
+pub fn new_day(day: u8, title: Option<&str>) -> Result<(), String> {
+   for path in scaffold(Path::new(""), day, title)? {
+      println!("Wrote {}", path.display());
+   }
+
+   Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn registers_days_in_order() {
+      let lib = "pub mod answers;\npub mod day01;\npub mod day03;\npub mod error;\n";
+      assert_eq!(
+         register_module(lib, 2).unwrap(),
+         "pub mod answers;\npub mod day01;\npub mod day02;\npub mod day03;\npub mod error;\n"
+      );
+
+      let registry = "use crate::{day01, day03};\n\npub const SOLUTIONS: &[&dyn DynSolution] = &[\n   &day01::Day01,\n   &day03::Day03,\n];\n";
+      assert_eq!(
+         register_solution(registry, 2).unwrap(),
+         "use crate::{day01, day02, day03};\n\npub const SOLUTIONS: &[&dyn DynSolution] = &[\n   &day01::Day01,\n   &day02::Day02,\n   &day03::Day03,\n];\n"
+      );
+
+      assert!(register_module(lib, 3).is_err());
+   }
+}
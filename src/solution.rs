@@ -0,0 +1,125 @@
+use std::fmt::{self, Debug, Display};
+
+use crate::error::ParseError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+   One,
+   Two,
+}
+
+impl Part {
+   pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+   pub fn number(self) -> u8 {
+      match self {
+         Part::One => 1,
+         Part::Two => 2,
+      }
+   }
+}
+
+impl Display for Part {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "{}", self.number())
+   }
+}
+
+/// The answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+   pub value: String,
+   /// The type the solution returned, e.g. `u32` or `String`.
+   pub type_name: &'static str,
+}
+
+impl Answer {
+   fn new<T: Display>(value: T) -> Self {
+      let type_name = std::any::type_name::<T>();
+
+      Answer {
+         value: value.to_string(),
+         type_name: type_name.rsplit("::").next().unwrap_or(type_name),
+      }
+   }
+}
+
+impl Display for Answer {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      write!(f, "{}", self.value)
+   }
+}
+
+/// A solution to one day of the puzzle.
+///
+/// The input is parsed once and then shared by both parts, so neither part
+/// may consume or mutate it. The parts may run on different threads at the
+/// same time, hence the `Send + Sync` bound on the input. The input is also
+/// `Debug`, so that its parsed structure can be inspected.
+pub trait Solution {
+   const DAY: u8;
+
+   type Input: Debug + Send + Sync + 'static;
+   type PartOne: Display;
+   type PartTwo: Display;
+
+   fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+   fn part_one(input: &Self::Input) -> Self::PartOne;
+
+   fn part_two(input: &Self::Input) -> Self::PartTwo;
+
+   /// Draws the input, or the state reached by solving it, for days where a
+   /// picture says more than the answers.
+   fn visualize(_input: &Self::Input) -> Option<String> {
+      None
+   }
+}
+
+/// An object-safe view of a [`Solution`], so that every day can be stored in
+/// the registry and driven generically.
+pub trait DynSolution: Sync {
+   fn day(&self) -> u8;
+
+   fn parse(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError>;
+}
+
+/// A parsed puzzle input, ready to be solved.
+pub trait ParsedInput: Send + Sync {
+   fn solve(&self, part: Part) -> Answer;
+
+   /// The parsed structure, pretty-printed with `Debug`.
+   fn describe(&self) -> String;
+
+   fn visualize(&self) -> Option<String>;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+   fn solve(&self, part: Part) -> Answer {
+      match part {
+         Part::One => Answer::new(S::part_one(&self.0)),
+         Part::Two => Answer::new(S::part_two(&self.0)),
+      }
+   }
+
+   fn describe(&self) -> String {
+      format!("{:#?}", self.0)
+   }
+
+   fn visualize(&self) -> Option<String> {
+      S::visualize(&self.0)
+   }
+}
+
+impl<S: Solution + Sync + 'static> DynSolution for S {
+   fn day(&self) -> u8 {
+      S::DAY
+   }
+
+   fn parse(&self, input: &str) -> Result<Box<dyn ParsedInput>, ParseError> {
+      let parsed = S::parse(input)?;
+      Ok(Box::new(Parsed::<S>(parsed)))
+   }
+}
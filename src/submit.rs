@@ -0,0 +1,345 @@
+//! Submits answers and remembers the wrong ones.
+
+use std::{
+   collections::BTreeMap,
+   fmt::{self, Display},
+   fs,
+   path::Path,
+   time::Duration,
+};
+
+use regex::Regex;
+use toml::{Table, Value};
+
+use crate::{
+   client::{Client, Config, CONFIG_PATH},
+   runner,
+   solution::Part,
+};
+
+/// Where wrong guesses are remembered, keyed as `[dayNN.partN] "answer" = "too low"`.
+pub const GUESSES_PATH: &str = "guesses.toml";
+
+/// What the website made of a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Response {
+   Correct,
+   TooHigh,
+   TooLow,
+   /// Wrong, without a hint as to which way.
+   Wrong,
+   /// An answer was submitted too recently; try again after this long.
+   Wait(Duration),
+   AlreadySolved,
+   Unrecognized,
+}
+
+impl Response {
+   /// Reads the response out of the page returned after submitting.
+   pub fn parse(html: &str) -> Self {
+      if html.contains("That's the right answer") {
+         return Response::Correct;
+      }
+
+      if html.contains("your answer is too high") {
+         return Response::TooHigh;
+      }
+
+      if html.contains("your answer is too low") {
+         return Response::TooLow;
+      }
+
+      if html.contains("That's not the right answer") {
+         return Response::Wrong;
+      }
+
+      let wait = Regex::new(r"(?:(\d+)m )?(\d+)s left to wait").unwrap();
+      if let Some(captures) = wait.captures(html) {
+         let minutes = captures
+            .get(1)
+            .map_or(0, |minutes| minutes.as_str().parse().unwrap_or(0));
+         let seconds = captures[2].parse().unwrap_or(0);
+         return Response::Wait(Duration::from_secs(minutes * 60 + seconds));
+      }
+
+      if html.contains("Did you already complete it?") {
+         return Response::AlreadySolved;
+      }
+
+      Response::Unrecognized
+   }
+
+   /// Whether the answer is definitely wrong, and so worth remembering.
+   pub fn is_wrong(&self) -> bool {
+      matches!(self, Response::TooHigh | Response::TooLow | Response::Wrong)
+   }
+}
+
+impl Display for Response {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      match self {
+         Response::Correct => write!(f, "correct"),
+         Response::TooHigh => write!(f, "too high"),
+         Response::TooLow => write!(f, "too low"),
+         Response::Wrong => write!(f, "wrong"),
+         Response::Wait(duration) => write!(f, "wait {}s", duration.as_secs()),
+         Response::AlreadySolved => write!(f, "already solved"),
+         Response::Unrecognized => write!(f, "unrecognized response"),
+      }
+   }
+}
+
+/// Every wrong answer submitted so far, per day and part.
+#[derive(Debug, Default)]
+pub struct Guesses(BTreeMap<(u8, Part), BTreeMap<String, Response>>);
+
+impl Guesses {
+   /// Loads the history, which is empty until the first wrong guess.
+   pub fn load(path: &Path) -> Result<Self, String> {
+      match fs::read_to_string(path) {
+         Ok(text) => Self::parse(&text)
+            .map_err(|error| format!("could not parse {}: {}", path.display(), error)),
+         Err(_) => Ok(Guesses::default()),
+      }
+   }
+
+   pub fn parse(text: &str) -> Result<Self, String> {
+      let table = text.parse::<Table>().map_err(|error| error.to_string())?;
+      let mut guesses = BTreeMap::new();
+
+      for (section, parts) in table {
+         let day = section
+            .strip_prefix("day")
+            .and_then(|day| day.parse::<u8>().ok())
+            .ok_or_else(|| format!("`[{}]` is not a day (expected e.g. `[day01]`)", section))?;
+
+         let parts = parts
+            .as_table()
+            .ok_or_else(|| format!("`{}` should be a table of parts", section))?;
+
+         for (key, answers) in parts {
+            let part = match key.as_str() {
+               "part1" => Part::One,
+               "part2" => Part::Two,
+               _ => {
+                  return Err(format!(
+                     "`{}.{}` is not a part (expected part1 or part2)",
+                     section, key
+                  ))
+               }
+            };
+
+            let answers = answers
+               .as_table()
+               .ok_or_else(|| format!("`{}.{}` should be a table of guesses", section, key))?;
+
+            for (answer, response) in answers {
+               let response = match response.as_str() {
+                  Some("too high") => Response::TooHigh,
+                  Some("too low") => Response::TooLow,
+                  Some("wrong") => Response::Wrong,
+                  _ => {
+                     return Err(format!(
+                        "`{}.{}` should be \"too high\", \"too low\" or \"wrong\"",
+                        section, key
+                     ))
+                  }
+               };
+
+               guesses
+                  .entry((day, part))
+                  .or_insert_with(BTreeMap::new)
+                  .insert(answer.clone(), response);
+            }
+         }
+      }
+
+      Ok(Guesses(guesses))
+   }
+
+   pub fn save(&self, path: &Path) -> Result<(), String> {
+      let mut table = Table::new();
+
+      for ((day, part), answers) in &self.0 {
+         let answers = answers
+            .iter()
+            .map(|(answer, response)| (answer.clone(), Value::String(response.to_string())))
+            .collect::<Table>();
+
+         table
+            .entry(format!("day{:02}", day))
+            .or_insert_with(|| Value::Table(Table::new()))
+            .as_table_mut()
+            .unwrap()
+            .insert(format!("part{}", part), Value::Table(answers));
+      }
+
+      let text = format!(
+         "# Wrong answers submitted with `cargo run -- submit`, never sent twice.\n\n{}",
+         table
+      );
+
+      fs::write(path, text)
+         .map_err(|error| format!("could not write {}: {}", path.display(), error))
+   }
+
+   pub fn record(&mut self, day: u8, part: Part, answer: &str, response: Response) {
+      self
+         .0
+         .entry((day, part))
+         .or_default()
+         .insert(answer.to_owned(), response);
+   }
+
+   /// Explains why an answer is already known to be wrong, either because it
+   /// was guessed before or because it is beyond an earlier guess that was
+   /// too high or too low.
+   pub fn rule_out(&self, day: u8, part: Part, answer: &str) -> Option<String> {
+      let guesses = self.0.get(&(day, part))?;
+
+      if let Some(response) = guesses.get(answer) {
+         return Some(format!(
+            "{} was already submitted and was {}",
+            answer, response
+         ));
+      }
+
+      let number = answer.parse::<i64>().ok()?;
+
+      guesses.iter().find_map(|(guess, response)| {
+         let guess_number = guess.parse::<i64>().ok()?;
+
+         match response {
+            Response::TooHigh if number >= guess_number => {
+               Some(format!("{} was too high, so {} is too", guess, answer))
+            }
+            Response::TooLow if number <= guess_number => {
+               Some(format!("{} was too low, so {} is too", guess, answer))
+            }
+            _ => None,
+         }
+      })
+   }
+}
+
+/// Submits an answer unless it is already known to be wrong, remembering it
+/// if it turns out to be wrong.
+pub fn submit(
+   client: &Client,
+   guesses: &mut Guesses,
+   day: u8,
+   part: Part,
+   answer: &str,
+) -> Result<Response, String> {
+   if let Some(reason) = guesses.rule_out(day, part, answer) {
+      return Err(format!("not submitting: {}", reason));
+   }
+
+   let html = client.post_form(
+      &format!("/2022/day/{}/answer", day),
+      &[("level", &part.to_string()), ("answer", answer)],
+   )?;
+
+   let response = Response::parse(&html);
+   if response.is_wrong() {
+      guesses.record(day, part, answer, response.clone());
+   }
+
+   Ok(response)
+}
+
+/// Solves a part, or takes the given answer, and submits it with the
+/// settings from `AOC_SESSION` or `aoc.toml`, keeping `guesses.toml` up to
+/// date. Anything but a correct answer is an error.
+pub fn submit_answer(day: u8, part: Part, answer: Option<String>) -> Result<(), String> {
+   let answer = match answer {
+      Some(answer) => answer,
+      None => runner::solve(day, part)?.value,
+   };
+
+   if answer.contains('\n') {
+      return Err(format!(
+         "the answer to day {} part {} is a picture; read it and pass it with --answer",
+         day, part
+      ));
+   }
+
+   let client = Client::new(Config::load(Path::new(CONFIG_PATH))?);
+   let mut guesses = Guesses::load(Path::new(GUESSES_PATH))?;
+
+   let response = submit(&client, &mut guesses, day, part, &answer)?;
+   if response.is_wrong() {
+      guesses.save(Path::new(GUESSES_PATH))?;
+   }
+
+   match response {
+      Response::Correct => {
+         println!("Day {} part {}: {} is correct!", day, part, answer);
+         Ok(())
+      }
+      Response::Wait(duration) => Err(format!(
+         "an answer was submitted too recently; try again in {}s",
+         duration.as_secs()
+      )),
+      response => Err(format!(
+         "day {} part {}: {} was {}",
+         day, part, answer, response
+      )),
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn parses_responses() {
+      let cases = [
+         (
+            "<p>That's the right answer! You are one gold star closer.</p>",
+            Response::Correct,
+         ),
+         (
+            "<p>That's not the right answer; your answer is too high.</p>",
+            Response::TooHigh,
+         ),
+         (
+            "<p>That's not the right answer; your answer is too low.</p>",
+            Response::TooLow,
+         ),
+         (
+            "<p>That's not the right answer. If you're stuck...</p>",
+            Response::Wrong,
+         ),
+         (
+            "<p>You gave an answer too recently. You have 4m 58s left to wait.</p>",
+            Response::Wait(Duration::from_secs(298)),
+         ),
+         (
+            "<p>You have 37s left to wait.</p>",
+            Response::Wait(Duration::from_secs(37)),
+         ),
+         (
+            "<p>You don't seem to be solving the right level. Did you already complete it?</p>",
+            Response::AlreadySolved,
+         ),
+         ("<p>Something else entirely.</p>", Response::Unrecognized),
+      ];
+
+      for (html, expected) in cases {
+         assert_eq!(Response::parse(html), expected, "{}", html);
+      }
+   }
+
+   #[test]
+   fn rules_out_answers_beyond_earlier_guesses() {
+      let guesses =
+         Guesses::parse("[day09.part2]\n\"2000\" = \"too low\"\n\"3000\" = \"too high\"\n")
+            .unwrap();
+
+      assert!(guesses.rule_out(9, Part::Two, "1999").is_some());
+      assert!(guesses.rule_out(9, Part::Two, "3000").is_some());
+      assert_eq!(guesses.rule_out(9, Part::Two, "2578"), None);
+      assert_eq!(guesses.rule_out(9, Part::One, "1999"), None);
+   }
+}
@@ -0,0 +1,161 @@
+use std::{
+   fmt::{self, Display},
+   time::{Duration, Instant},
+};
+
+use crate::solution::Part;
+
+/// Repeated measurements of the same piece of work.
+#[derive(Clone, Debug, Default)]
+pub struct Samples(Vec<Duration>);
+
+impl Samples {
+   /// Runs `work`, recording how long it took.
+   pub fn record<T>(&mut self, work: impl FnOnce() -> T) -> T {
+      let start = Instant::now();
+      let result = work();
+      self.0.push(start.elapsed());
+      result
+   }
+
+   /// The time spent across every sample.
+   pub fn total(&self) -> Duration {
+      self.0.iter().sum()
+   }
+
+   pub fn min(&self) -> Duration {
+      self.0.iter().copied().min().unwrap_or_default()
+   }
+
+   pub fn max(&self) -> Duration {
+      self.0.iter().copied().max().unwrap_or_default()
+   }
+
+   pub fn median(&self) -> Duration {
+      let mut samples = self.0.clone();
+      samples.sort();
+
+      match samples.len() {
+         0 => Duration::ZERO,
+         len if len % 2 == 1 => samples[len / 2],
+         len => (samples[len / 2 - 1] + samples[len / 2]) / 2,
+      }
+   }
+}
+
+impl Display for Samples {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      if self.0.len() <= 1 {
+         return write!(f, "{}", format_duration(self.median()));
+      }
+
+      write!(
+         f,
+         "median {}, min {}, max {}",
+         format_duration(self.median()),
+         format_duration(self.min()),
+         format_duration(self.max())
+      )
+   }
+}
+
+/// How long it took to parse and solve one day.
+#[derive(Clone, Debug)]
+pub struct DayTimings {
+   pub day: u8,
+   pub parse: Samples,
+   pub parts: Vec<(Part, Samples)>,
+}
+
+impl DayTimings {
+   pub fn new(day: u8) -> Self {
+      DayTimings {
+         day,
+         parse: Samples::default(),
+         parts: vec![],
+      }
+   }
+
+   /// How long a part took, if it was solved.
+   pub fn part(&self, part: Part) -> Option<&Samples> {
+      self
+         .parts
+         .iter()
+         .find(|(other, _)| *other == part)
+         .map(|(_, samples)| samples)
+   }
+
+   /// The wall-clock time of every parse and solve, summed whichever thread
+   /// each ran on. When they run in parallel this adds up to more than the
+   /// time the whole run took, but it is not CPU time: a task that waits
+   /// still counts.
+   pub fn task_time(&self) -> Duration {
+      self.parse.total()
+         + self
+            .parts
+            .iter()
+            .map(|(_, samples)| samples.total())
+            .sum::<Duration>()
+   }
+
+   /// The typical time taken to parse the input and solve every part.
+   pub fn total(&self) -> Duration {
+      self.parse.median()
+         + self
+            .parts
+            .iter()
+            .map(|(_, samples)| samples.median())
+            .sum::<Duration>()
+   }
+}
+
+/// Formats a duration with a unit that keeps it short and readable.
+pub fn format_duration(duration: Duration) -> String {
+   let nanos = duration.as_nanos();
+
+   if nanos < 1_000 {
+      format!("{}ns", nanos)
+   } else if nanos < 1_000_000 {
+      format!("{:.2}µs", nanos as f64 / 1e3)
+   } else if nanos < 1_000_000_000 {
+      format!("{:.2}ms", nanos as f64 / 1e6)
+   } else {
+      format!("{:.2}s", nanos as f64 / 1e9)
+   }
+}
+
+/// Prints a table of every day's timings, slowest first.
+pub fn print_summary(timings: &[DayTimings]) {
+   let mut timings = timings.iter().collect::<Vec<_>>();
+   timings.sort_by_key(|timings| std::cmp::Reverse(timings.total()));
+
+   let cell = |samples: Option<&Samples>| match samples {
+      Some(samples) => format_duration(samples.median()),
+      None => "-".to_owned(),
+   };
+
+   println!(
+      "{:>4}  {:>10}  {:>10}  {:>10}  {:>10}",
+      "Day", "Parse", "Part 1", "Part 2", "Total"
+   );
+
+   for day in &timings {
+      println!(
+         "{:>4}  {:>10}  {:>10}  {:>10}  {:>10}",
+         day.day,
+         cell(Some(&day.parse)),
+         cell(day.part(Part::One)),
+         cell(day.part(Part::Two)),
+         format_duration(day.total())
+      );
+   }
+
+   println!(
+      "{:>4}  {:>10}  {:>10}  {:>10}  {:>10}",
+      "All",
+      "",
+      "",
+      "",
+      format_duration(timings.iter().map(|day| day.total()).sum())
+   );
+}
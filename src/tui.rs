@@ -0,0 +1,474 @@
+//! An interactive dashboard for browsing the days, their answers, inputs and
+//! visualizations.
+
+use std::{
+   any::Any,
+   panic::{self, AssertUnwindSafe},
+   sync::{
+      mpsc::{self, Receiver, Sender},
+      Arc,
+   },
+   thread,
+   time::{Duration, Instant},
+};
+
+use ratatui::{
+   crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+   layout::{Constraint, Layout, Rect},
+   style::{Modifier, Style},
+   widgets::{Block, Paragraph, Row, Table, TableState, Tabs},
+   DefaultTerminal, Frame,
+};
+
+use crate::{
+   answers::{Answers, Verdict, ANSWERS_PATH},
+   input::{default_input_path, read_input},
+   registry::SOLUTIONS,
+   runner::format_parse_error,
+   solution::{Answer, DynSolution, Part},
+   timing::format_duration,
+};
+
+/// How far the left and right arrows scroll the details sideways.
+const COLUMN_STEP: usize = 8;
+
+/// Everything shown for a day once it has been solved.
+struct Report {
+   input: String,
+   structure: String,
+   visualization: Option<String>,
+   parse_time: Duration,
+   answers: Vec<(Part, Answer, Duration)>,
+}
+
+impl Report {
+   fn total_time(&self) -> Duration {
+      self.parse_time
+         + self
+            .answers
+            .iter()
+            .map(|(_, _, time)| *time)
+            .sum::<Duration>()
+   }
+}
+
+enum Status {
+   Solving,
+   Solved(Box<Report>),
+   Failed(String),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum View {
+   Answers,
+   Input,
+   Structure,
+   Visualization,
+}
+
+impl View {
+   const ALL: [View; 4] = [
+      View::Answers,
+      View::Input,
+      View::Structure,
+      View::Visualization,
+   ];
+
+   fn title(self) -> &'static str {
+      match self {
+         View::Answers => "Answers",
+         View::Input => "Input",
+         View::Structure => "Parsed",
+         View::Visualization => "Visualization",
+      }
+   }
+
+   fn index(self) -> usize {
+      View::ALL.iter().position(|&view| view == self).unwrap()
+   }
+}
+
+/// Reads, parses and solves a day, keeping everything the dashboard shows.
+fn solve(solution: &dyn DynSolution) -> Status {
+   let path = default_input_path(solution.day());
+   let input = match read_input(&path) {
+      Ok(input) => input,
+      Err(error) => return Status::Failed(error),
+   };
+
+   let start = Instant::now();
+   let parsed = match solution.parse(&input) {
+      Ok(parsed) => parsed,
+      Err(error) => return Status::Failed(format_parse_error(&error, &path, &input)),
+   };
+   let parse_time = start.elapsed();
+
+   let answers = Part::ALL
+      .into_iter()
+      .map(|part| {
+         let start = Instant::now();
+         let answer = parsed.solve(part);
+         (part, answer, start.elapsed())
+      })
+      .collect();
+
+   Status::Solved(Box::new(Report {
+      structure: parsed.describe(),
+      visualization: parsed.visualize(),
+      input,
+      parse_time,
+      answers,
+   }))
+}
+
+/// The message a solver panicked with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+   let message = payload
+      .downcast_ref::<&str>()
+      .copied()
+      .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+      .unwrap_or("no message");
+
+   format!("the solver panicked: {}", message)
+}
+
+/// The lines of `text` that fit in a `height` line window scrolled to
+/// `(row, column)`.
+fn window(text: &str, (row, column): (usize, usize), height: usize) -> String {
+   text
+      .lines()
+      .skip(row)
+      .take(height)
+      .map(|line| line.chars().skip(column).collect::<String>())
+      .collect::<Vec<_>>()
+      .join("\n")
+}
+
+struct App {
+   days: Vec<(&'static dyn DynSolution, Status)>,
+   /// How many times each day has been solved, so that the result of an
+   /// older run that finishes late can be told apart and dropped.
+   generations: Vec<u64>,
+   expected: Answers,
+   table: TableState,
+   view: View,
+   /// The row and column the details are scrolled to.
+   scroll: (usize, usize),
+   /// How many lines of details fit on screen, as of the last frame.
+   page: usize,
+   sender: Sender<(usize, u64, Status)>,
+   receiver: Receiver<(usize, u64, Status)>,
+}
+
+impl App {
+   fn new() -> Self {
+      let (sender, receiver) = mpsc::channel();
+
+      let mut app = App {
+         days: SOLUTIONS
+            .iter()
+            .map(|&solution| (solution, Status::Solving))
+            .collect(),
+         generations: vec![0; SOLUTIONS.len()],
+         // Days without an expected answer just go unchecked.
+         expected: Answers::load(ANSWERS_PATH.as_ref()).unwrap_or_default(),
+         table: TableState::default().with_selected(0),
+         view: View::Answers,
+         scroll: (0, 0),
+         page: 0,
+         sender,
+         receiver,
+      };
+
+      for index in 0..app.days.len() {
+         app.rerun(index);
+      }
+
+      app
+   }
+
+   /// Solves a day again on another thread, e.g. after its input changed.
+   fn rerun(&mut self, index: usize) {
+      let solution = self.days[index].0;
+      self.days[index].1 = Status::Solving;
+      self.generations[index] += 1;
+      let generation = self.generations[index];
+
+      let sender = self.sender.clone();
+      thread::spawn(move || {
+         // A panicking solver fails its day rather than leaving it solving
+         // forever.
+         let status = panic::catch_unwind(AssertUnwindSafe(|| solve(solution)))
+            .unwrap_or_else(|payload| Status::Failed(panic_message(&*payload)));
+
+         // The dashboard may have quit in the meantime.
+         let _ = sender.send((index, generation, status));
+      });
+   }
+
+   /// Shows a day's result, unless the day has been re-run since.
+   fn receive(&mut self, index: usize, generation: u64, status: Status) {
+      if generation == self.generations[index] {
+         self.days[index].1 = status;
+      }
+   }
+
+   fn selected(&self) -> usize {
+      self.table.selected().unwrap_or(0)
+   }
+
+   /// The text of the current view for the selected day.
+   fn details(&self) -> String {
+      let (solution, status) = &self.days[self.selected()];
+      let day = solution.day();
+
+      let report = match status {
+         Status::Solving => return format!("Solving day {}...", day),
+         Status::Failed(error) => return format!("error: {}", error),
+         Status::Solved(report) => report,
+      };
+
+      match self.view {
+         View::Answers => {
+            let mut text = format!("Parsed in {}\n", format_duration(report.parse_time));
+
+            for (part, answer, time) in &report.answers {
+               let check = match self.expected.check(day, *part, &answer.value) {
+                  Verdict::Pass => "matches answers.toml".to_owned(),
+                  Verdict::Fail { expected } => format!("expected {}", expected.trim_end()),
+                  Verdict::Unknown => "no expected answer".to_owned(),
+               };
+
+               text += &format!(
+                  "\nPart {} ({}, {}, {}):\n{}\n",
+                  part,
+                  answer.type_name,
+                  format_duration(*time),
+                  check,
+                  answer.value.trim_end()
+               );
+            }
+
+            text
+         }
+         View::Input => report.input.clone(),
+         View::Structure => report.structure.clone(),
+         View::Visualization => report
+            .visualization
+            .clone()
+            .unwrap_or_else(|| format!("Day {} has no visualization.", day)),
+      }
+   }
+
+   fn summary(&self, day: u8, status: &Status) -> Row<'static> {
+      let report = match status {
+         Status::Solving => return Row::new(vec![format!("{:>3}", day), "...".to_owned()]),
+         Status::Failed(_) => return Row::new(vec![format!("{:>3}", day), "error".to_owned()]),
+         Status::Solved(report) => report,
+      };
+
+      let mut cells = vec![format!("{:>3}", day)];
+
+      for (part, answer, _) in &report.answers {
+         let value = if answer.value.contains('\n') {
+            "(picture)"
+         } else {
+            &answer.value
+         };
+
+         let mark = match self.expected.check(day, *part, &answer.value) {
+            Verdict::Pass => " ✓",
+            Verdict::Fail { .. } => " ✗",
+            Verdict::Unknown => "",
+         };
+
+         cells.push(format!("{}{}", value, mark));
+      }
+
+      cells.push(format_duration(report.total_time()));
+      Row::new(cells)
+   }
+
+   fn draw(&mut self, frame: &mut Frame) {
+      let [main, help] =
+         Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+      let [list, details] =
+         Layout::horizontal([Constraint::Length(56), Constraint::Min(0)]).areas(main);
+
+      let rows = self
+         .days
+         .iter()
+         .map(|(solution, status)| self.summary(solution.day(), status))
+         .collect::<Vec<_>>();
+
+      let table = Table::new(
+         rows,
+         [
+            Constraint::Length(3),
+            Constraint::Length(18),
+            Constraint::Length(18),
+            Constraint::Length(10),
+         ],
+      )
+      .header(Row::new(["Day", "Part 1", "Part 2", "Time"]).style(Style::new().bold()))
+      .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+      .block(Block::bordered().title(" Advent of Code 2022 "));
+
+      frame.render_stateful_widget(table, list, &mut self.table);
+      self.draw_details(frame, details);
+
+      frame.render_widget(
+         Paragraph::new(
+            " ↑/↓ day  tab view  pgup/pgdn/home scroll  ←/→ pan  r re-run  R re-run all  q quit",
+         ),
+         help,
+      );
+   }
+
+   fn draw_details(&mut self, frame: &mut Frame, area: Rect) {
+      let [tabs, body] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+
+      frame.render_widget(
+         Tabs::new(View::ALL.map(View::title))
+            .select(self.view.index())
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+         tabs,
+      );
+
+      let day = self.days[self.selected()].0.day();
+      let block = Block::bordered().title(format!(" Day {}: {} ", day, self.view.title()));
+      self.page = block.inner(body).height as usize;
+
+      let text = window(&self.details(), self.scroll, self.page);
+      frame.render_widget(Paragraph::new(text).block(block), body);
+   }
+
+   fn select(&mut self, index: usize) {
+      self.table.select(Some(index));
+      self.scroll = (0, 0);
+   }
+
+   fn show(&mut self, view: View) {
+      self.view = view;
+      self.scroll = (0, 0);
+   }
+
+   fn scroll_by(&mut self, rows: isize) {
+      let last_row = self.details().lines().count().saturating_sub(1);
+      self.scroll.0 = self.scroll.0.saturating_add_signed(rows).min(last_row);
+   }
+
+   /// Handles a key press, returning whether to quit.
+   fn handle(&mut self, key: KeyEvent) -> bool {
+      let selected = self.selected();
+      let view = self.view.index();
+      let page = self.page.max(1) as isize;
+
+      match key.code {
+         KeyCode::Char('q') | KeyCode::Esc => return true,
+         KeyCode::Down | KeyCode::Char('j') => self.select((selected + 1) % self.days.len()),
+         KeyCode::Up | KeyCode::Char('k') => {
+            self.select((selected + self.days.len() - 1) % self.days.len())
+         }
+         KeyCode::Tab => self.show(View::ALL[(view + 1) % View::ALL.len()]),
+         KeyCode::BackTab => self.show(View::ALL[(view + View::ALL.len() - 1) % View::ALL.len()]),
+         KeyCode::PageDown | KeyCode::Char(' ') => self.scroll_by(page),
+         KeyCode::PageUp => self.scroll_by(-page),
+         KeyCode::Home => self.scroll = (0, 0),
+         KeyCode::Right => self.scroll.1 += COLUMN_STEP,
+         KeyCode::Left => self.scroll.1 = self.scroll.1.saturating_sub(COLUMN_STEP),
+         KeyCode::Char('r') => self.rerun(selected),
+         KeyCode::Char('R') => (0..self.days.len()).for_each(|index| self.rerun(index)),
+         _ => (),
+      }
+
+      false
+   }
+
+   fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<(), String> {
+      let io_error = |error: std::io::Error| format!("terminal error: {}", error);
+
+      loop {
+         while let Ok((index, generation, status)) = self.receiver.try_recv() {
+            self.receive(index, generation, status);
+         }
+
+         terminal.draw(|frame| self.draw(frame)).map_err(io_error)?;
+
+         // Wake up regularly to show days as they are solved.
+         if event::poll(Duration::from_millis(100)).map_err(io_error)? {
+            if let Event::Key(key) = event::read().map_err(io_error)? {
+               if key.kind == KeyEventKind::Press && self.handle(key) {
+                  return Ok(());
+               }
+            }
+         }
+      }
+   }
+}
+
+/// Runs the dashboard until it is quit, solving every day in the background.
+pub fn run() -> Result<(), String> {
+   let original = Arc::new(panic::take_hook());
+   let mut terminal = ratatui::init();
+
+   // The hook ratatui installs restores the terminal on any panic, but a
+   // solver's panic is caught and shown as its day failing, so only the
+   // dashboard's own panics should restore the terminal and reach the
+   // original hook.
+   drop(panic::take_hook());
+   let dashboard = thread::current().id();
+   let hook = Arc::clone(&original);
+   panic::set_hook(Box::new(move |info| {
+      if thread::current().id() == dashboard {
+         ratatui::restore();
+         hook(info);
+      }
+   }));
+
+   let result = App::new().run(&mut terminal);
+   ratatui::restore();
+
+   panic::set_hook(Box::new(move |info| original(info)));
+
+   result
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   #[test]
+   fn shows_the_scrolled_window_of_the_details() {
+      let text = "line one\nline two\nline three\nline four";
+
+      assert_eq!(window(text, (0, 0), 2), "line one\nline two");
+      assert_eq!(window(text, (1, 5), 2), "two\nthree");
+      assert_eq!(window(text, (3, 0), 5), "line four");
+   }
+
+   #[test]
+   fn reports_what_a_solver_panicked_with() {
+      let payload = panic::catch_unwind(|| panic!("no program loaded")).unwrap_err();
+      assert_eq!(
+         panic_message(&*payload),
+         "the solver panicked: no program loaded"
+      );
+
+      let payload = panic::catch_unwind(|| panic!("day {}", 10)).unwrap_err();
+      assert_eq!(panic_message(&*payload), "the solver panicked: day 10");
+   }
+
+   #[test]
+   fn drops_results_from_older_runs() {
+      // Every day is solved once on start-up, then day 1 is re-run.
+      let mut app = App::new();
+      app.rerun(0);
+
+      app.receive(0, 1, Status::Failed("stale".to_owned()));
+      assert!(matches!(app.days[0].1, Status::Solving));
+
+      app.receive(0, 2, Status::Failed("latest".to_owned()));
+      assert!(matches!(&app.days[0].1, Status::Failed(error) if error == "latest"));
+   }
+}
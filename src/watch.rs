@@ -0,0 +1,264 @@
+//! Re-solves a day whenever its source or input changes.
+//!
+//! The day is solved by a child `cargo run`, so a change to `src/dayNN.rs`
+//! rebuilds the binary first, while a change to `input/dayNN.txt` is picked up
+//! without a rebuild because inputs are read at runtime.
+
+use std::{
+   env, fs,
+   path::{Path, PathBuf},
+   process::{Command, Stdio},
+   thread,
+   time::{Duration, SystemTime},
+};
+
+use crate::{input::default_input_path, runner, timing::format_duration};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Returns the location of the source file for a day.
+pub fn default_source_path(day: u8) -> PathBuf {
+   PathBuf::from(format!("src/day{:02}.rs", day))
+}
+
+/// A file whose modification time is compared between polls.
+struct WatchedFile {
+   path: PathBuf,
+   modified: Option<SystemTime>,
+}
+
+impl WatchedFile {
+   fn new(path: PathBuf) -> Self {
+      let modified = modified(&path);
+      WatchedFile { path, modified }
+   }
+
+   /// Whether the file was modified, created or deleted since the last call.
+   fn changed(&mut self) -> bool {
+      let modified = modified(&self.path);
+      let changed = modified != self.modified;
+      self.modified = modified;
+      changed
+   }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+   fs::metadata(path)
+      .and_then(|metadata| metadata.modified())
+      .ok()
+}
+
+/// The answer to one part, as reported by the child process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchedAnswer {
+   pub part: u8,
+   pub answer: String,
+   pub elapsed: Duration,
+}
+
+/// Solves a day by running this crate's binary through cargo, rebuilding it
+/// first if its source changed. Build errors are shown as cargo prints them.
+fn solve(day: u8) -> Result<Vec<WatchedAnswer>, String> {
+   let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+
+   let mut command = Command::new(cargo);
+   command.args(["run", "--quiet"]);
+   if !cfg!(debug_assertions) {
+      command.arg("--release");
+   }
+
+   let output = command
+      .args(["--", "run", &day.to_string(), "--format", "json"])
+      .stderr(Stdio::inherit())
+      .output()
+      .map_err(|error| format!("could not run cargo: {}", error))?;
+
+   if !output.status.success() {
+      return Err(format!("day {} could not be built or solved", day));
+   }
+
+   parse_answers(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Reads the answers out of the JSON printed by `run --format json`.
+fn parse_answers(json: &str) -> Result<Vec<WatchedAnswer>, String> {
+   let invalid = || "could not read the answers from `run --format json`".to_owned();
+   let records: serde_json::Value = serde_json::from_str(json).map_err(|_| invalid())?;
+
+   records
+      .as_array()
+      .ok_or_else(invalid)?
+      .iter()
+      .map(|record| {
+         Some(WatchedAnswer {
+            part: record["part"].as_u64()? as u8,
+            answer: record["answer"].as_str()?.to_owned(),
+            elapsed: Duration::from_nanos(record["elapsed_ns"].as_u64()?),
+         })
+      })
+      .collect::<Option<Vec<_>>>()
+      .ok_or_else(invalid)
+}
+
+/// Formats the answers, comparing each one with the previous answer to the
+/// same part. Multi-line answers are compared line by line.
+pub fn format_answers(previous: &[WatchedAnswer], current: &[WatchedAnswer]) -> String {
+   let mut output = String::new();
+
+   for answer in current {
+      let elapsed = format_duration(answer.elapsed);
+      let previous = previous
+         .iter()
+         .find(|previous| previous.part == answer.part)
+         .map(|previous| previous.answer.as_str());
+
+      if !answer.answer.contains('\n') {
+         let note = match previous {
+            None => String::new(),
+            Some(previous) if previous == answer.answer => " (unchanged)".to_owned(),
+            Some(previous) => format!(" (was {})", previous.trim_end()),
+         };
+
+         output += &format!(
+            "Part {}: {}{} [{}]\n",
+            answer.part, answer.answer, note, elapsed
+         );
+         continue;
+      }
+
+      match previous {
+         Some(previous) if previous != answer.answer => {
+            output += &format!("Part {} (changed) [{}]:\n\n", answer.part, elapsed);
+            output += &line_diff(previous, &answer.answer);
+         }
+         Some(_) => {
+            output += &format!("Part {} (unchanged) [{}]:\n\n", answer.part, elapsed);
+            output += &indent(&answer.answer);
+         }
+         None => {
+            output += &format!("Part {} [{}]:\n\n", answer.part, elapsed);
+            output += &indent(&answer.answer);
+         }
+      }
+   }
+
+   output
+}
+
+fn indent(text: &str) -> String {
+   text.lines().map(|line| format!("  {}\n", line)).collect()
+}
+
+/// Compares two texts line by line, marking removed lines with `-` and added
+/// lines with `+`.
+pub fn line_diff(previous: &str, current: &str) -> String {
+   let previous = previous.lines().collect::<Vec<_>>();
+   let current = current.lines().collect::<Vec<_>>();
+   let mut diff = String::new();
+
+   for index in 0..previous.len().max(current.len()) {
+      match (previous.get(index), current.get(index)) {
+         (Some(old), Some(new)) if old == new => diff += &format!("  {}\n", new),
+         (old, new) => {
+            if let Some(old) = old {
+               diff += &format!("- {}\n", old);
+            }
+            if let Some(new) = new {
+               diff += &format!("+ {}\n", new);
+            }
+         }
+      }
+   }
+
+   diff
+}
+
+/// Solves a day, then solves it again every time its source or input
+/// changes, until interrupted.
+pub fn watch(day: u8) -> Result<(), String> {
+   runner::find_solution(day)?;
+
+   let mut files = [
+      WatchedFile::new(default_source_path(day)),
+      WatchedFile::new(default_input_path(day)),
+   ];
+
+   println!(
+      "Watching {} and {} (press Ctrl-C to stop)",
+      files[0].path.display(),
+      files[1].path.display()
+   );
+
+   let mut previous = vec![];
+
+   println!("\n=== Day {} ===", day);
+   show(day, &mut previous);
+
+   loop {
+      thread::sleep(POLL_INTERVAL);
+
+      let changed = files
+         .iter_mut()
+         .filter_map(|file| file.changed().then(|| file.path.display().to_string()))
+         .collect::<Vec<_>>();
+
+      if changed.is_empty() {
+         continue;
+      }
+
+      println!("\n=== Day {} ({} changed) ===", day, changed.join(", "));
+      show(day, &mut previous);
+   }
+}
+
+/// Solves the day and prints its answers, remembering them for next time.
+/// The previous answers are kept if the day could not be solved.
+fn show(day: u8, previous: &mut Vec<WatchedAnswer>) {
+   match solve(day) {
+      Ok(answers) => {
+         print!("{}", format_answers(previous, &answers));
+         *previous = answers;
+      }
+      Err(message) => println!("error: {}", message),
+   }
+}
+
+#[cfg(test)]
+mod tests {
+   use super::*;
+
+   fn answer(part: u8, answer: &str) -> WatchedAnswer {
+      WatchedAnswer {
+         part,
+         answer: answer.to_owned(),
+         elapsed: Duration::from_micros(5),
+      }
+   }
+
+   #[test]
+   fn reports_changed_answers() {
+      let previous = [answer(1, "13"), answer(2, "1")];
+      let current = [answer(1, "13"), answer(2, "36")];
+
+      assert_eq!(
+         format_answers(&previous, &current),
+         "Part 1: 13 (unchanged) [5.00µs]\nPart 2: 36 (was 1) [5.00µs]\n"
+      );
+   }
+
+   #[test]
+   fn diffs_multi_line_answers() {
+      assert_eq!(
+         line_diff("##..\n..##\n", "##..\n.###\n#"),
+         "  ##..\n- ..##\n+ .###\n+ #\n"
+      );
+   }
+
+   #[test]
+   fn reads_the_json_report() {
+      let json =
+         r#"[{ "day": 9, "part": 1, "answer": "13", "type": "usize", "elapsed_ns": 5000 }]"#;
+      assert_eq!(parse_answers(json), Ok(vec![answer(1, "13")]));
+   }
+}
@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use advent_of_code_2022::{
+   answers::{Answers, Verdict, ANSWERS_PATH},
+   input::{default_input_path, read_input},
+   registry::SOLUTIONS,
+   solution::Part,
+};
+
+#[test]
+fn every_day_matches_the_stored_answers() {
+   let answers = Answers::load(Path::new(ANSWERS_PATH)).unwrap();
+
+   for solution in SOLUTIONS {
+      let day = solution.day();
+      let input = read_input(&default_input_path(day)).unwrap();
+      let parsed = solution.parse(&input).unwrap();
+
+      for part in Part::ALL {
+         // Days that were scaffolded but not yet solved have no answers.
+         if answers.get(day, part).is_none() {
+            continue;
+         }
+
+         let answer = parsed.solve(part);
+         assert_eq!(
+            answers.check(day, part, &answer.value),
+            Verdict::Pass,
+            "day {} part {} answered {}",
+            day,
+            part,
+            answer
+         );
+      }
+   }
+}
@@ -0,0 +1,129 @@
+//! A stand-in for the Advent of Code website, serving canned responses.
+
+// Each test crate uses a different subset of these helpers.
+#![allow(dead_code)]
+
+use std::{
+   env, fs,
+   io::{BufRead, BufReader, Read, Write},
+   net::TcpListener,
+   path::PathBuf,
+   sync::{Arc, Mutex},
+   thread,
+   time::Duration,
+};
+
+use advent_of_code_2022::client::{Client, Config};
+
+/// A request received by the mock server.
+#[derive(Clone, Debug)]
+pub struct Request {
+   pub method: String,
+   pub path: String,
+   pub cookie: Option<String>,
+   pub body: String,
+}
+
+pub struct MockServer {
+   pub base_url: String,
+   requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+   /// Starts a server that answers each request with the next status and
+   /// body in `responses`.
+   pub fn start(responses: Vec<(u16, &'static str)>) -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let base_url = format!("http://{}", listener.local_addr().unwrap());
+      let requests = Arc::new(Mutex::new(vec![]));
+      let received = requests.clone();
+
+      thread::spawn(move || {
+         for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut parts = request_line.split_whitespace();
+            let method = parts.next().unwrap_or_default().to_owned();
+            let path = parts.next().unwrap_or_default().to_owned();
+
+            let mut cookie = None;
+            let mut content_length = 0;
+            loop {
+               let mut header = String::new();
+               reader.read_line(&mut header).unwrap();
+               let header = header.trim_end();
+               if header.is_empty() {
+                  break;
+               }
+
+               let (name, value) = header.split_once(':').unwrap();
+               match name.to_ascii_lowercase().as_str() {
+                  "cookie" => cookie = Some(value.trim().to_owned()),
+                  "content-length" => content_length = value.trim().parse().unwrap(),
+                  _ => (),
+               }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            received.lock().unwrap().push(Request {
+               method,
+               path,
+               cookie,
+               body: String::from_utf8(request_body).unwrap(),
+            });
+
+            write!(
+               reader.get_mut(),
+               "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+               status,
+               body.len(),
+               body
+            )
+            .unwrap();
+         }
+      });
+
+      MockServer { base_url, requests }
+   }
+
+   pub fn requests(&self) -> Vec<Request> {
+      self.requests.lock().unwrap().clone()
+   }
+
+   /// A client for this server that does not wait between requests.
+   pub fn client(&self, scratch: &Scratch) -> Client {
+      Client::new(Config {
+         base_url: self.base_url.clone(),
+         session: Some("secret".to_owned()),
+         min_interval: Duration::ZERO,
+         last_request_path: scratch.path("last-request"),
+      })
+   }
+}
+
+/// A temporary directory that is removed when dropped.
+pub struct Scratch(PathBuf);
+
+impl Scratch {
+   pub fn new(name: &str) -> Self {
+      let path = env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+      fs::remove_dir_all(&path).ok();
+      fs::create_dir_all(&path).unwrap();
+      Scratch(path)
+   }
+
+   pub fn path(&self, name: &str) -> PathBuf {
+      self.0.join(name)
+   }
+}
+
+impl Drop for Scratch {
+   fn drop(&mut self) {
+      fs::remove_dir_all(&self.0).ok();
+   }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use std::{
+   fs,
+   time::{Duration, Instant},
+};
+
+use advent_of_code_2022::{
+   client::{Client, Config},
+   fetch::fetch,
+};
+
+use common::{MockServer, Scratch};
+
+#[test]
+fn downloads_the_input_with_the_session_token() {
+   let scratch = Scratch::new("fetch-download");
+   let server = MockServer::start(vec![(200, "1000\n2000\n")]);
+   let path = scratch.path("input/day01.txt");
+
+   fetch(&server.client(&scratch), 1, &path, false).unwrap();
+
+   assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+   let requests = server.requests();
+   assert_eq!(requests.len(), 1);
+   assert_eq!(requests[0].method, "GET");
+   assert_eq!(requests[0].path, "/2022/day/1/input");
+   assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+}
+
+#[test]
+fn refuses_to_overwrite_an_input_without_force() {
+   let scratch = Scratch::new("fetch-overwrite");
+   let server = MockServer::start(vec![(200, "new input\n")]);
+   let path = scratch.path("day07.txt");
+   fs::write(&path, "old input\n").unwrap();
+
+   let error = fetch(&server.client(&scratch), 7, &path, false).unwrap_err();
+   assert!(error.contains("--force"), "{}", error);
+   assert_eq!(fs::read_to_string(&path).unwrap(), "old input\n");
+   assert!(server.requests().is_empty());
+
+   fetch(&server.client(&scratch), 7, &path, true).unwrap();
+   assert_eq!(fs::read_to_string(&path).unwrap(), "new input\n");
+}
+
+#[test]
+fn reports_rejected_requests() {
+   let scratch = Scratch::new("fetch-rejected");
+   let server = MockServer::start(vec![(400, "Puzzle inputs differ by user.")]);
+   let path = scratch.path("day02.txt");
+
+   let error = fetch(&server.client(&scratch), 2, &path, false).unwrap_err();
+   assert!(error.contains("session token"), "{}", error);
+   assert!(!path.exists());
+}
+
+#[test]
+fn waits_between_requests() {
+   let scratch = Scratch::new("fetch-rate-limit");
+   let server = MockServer::start(vec![(200, "first\n"), (200, "second\n")]);
+   let client = Client::new(Config {
+      base_url: server.base_url.clone(),
+      session: Some("secret".to_owned()),
+      min_interval: Duration::from_millis(300),
+      last_request_path: scratch.path("last-request"),
+   });
+
+   let start = Instant::now();
+   fetch(&client, 3, &scratch.path("day03.txt"), false).unwrap();
+   fetch(&client, 4, &scratch.path("day04.txt"), false).unwrap();
+
+   assert!(start.elapsed() >= Duration::from_millis(300));
+   assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::fs;
+
+use advent_of_code_2022::{
+   solution::Part,
+   submit::{submit, Guesses, Response},
+};
+
+use common::{MockServer, Scratch};
+
+#[test]
+fn submits_the_answer_for_the_part() {
+   let scratch = Scratch::new("submit-correct");
+   let server = MockServer::start(vec![(200, "<p>That's the right answer!</p>")]);
+   let mut guesses = Guesses::default();
+
+   let response = submit(&server.client(&scratch), &mut guesses, 9, Part::Two, "2578").unwrap();
+   assert_eq!(response, Response::Correct);
+
+   let requests = server.requests();
+   assert_eq!(requests.len(), 1);
+   assert_eq!(requests[0].method, "POST");
+   assert_eq!(requests[0].path, "/2022/day/9/answer");
+   assert_eq!(requests[0].cookie.as_deref(), Some("session=secret"));
+   assert_eq!(requests[0].body, "level=2&answer=2578");
+}
+
+#[test]
+fn never_sends_the_same_wrong_answer_twice() {
+   let scratch = Scratch::new("submit-wrong");
+   let server = MockServer::start(vec![(
+      200,
+      "<p>That's not the right answer; your answer is too low.</p>",
+   )]);
+   let client = server.client(&scratch);
+   let path = scratch.path("guesses.toml");
+
+   let mut guesses = Guesses::load(&path).unwrap();
+   let response = submit(&client, &mut guesses, 1, Part::One, "70000").unwrap();
+   assert_eq!(response, Response::TooLow);
+   guesses.save(&path).unwrap();
+
+   let saved = fs::read_to_string(&path).unwrap();
+   assert!(saved.contains("70000 = \"too low\""), "{}", saved);
+
+   let mut guesses = Guesses::load(&path).unwrap();
+   for answer in ["70000", "69999"] {
+      let error = submit(&client, &mut guesses, 1, Part::One, answer).unwrap_err();
+      assert!(error.contains("too low"), "{}", error);
+   }
+
+   assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn reports_how_long_to_wait() {
+   let scratch = Scratch::new("submit-wait");
+   let server = MockServer::start(vec![(
+      200,
+      "<p>You gave an answer too recently. You have 1m 5s left to wait.</p>",
+   )]);
+   let mut guesses = Guesses::default();
+
+   let response = submit(&server.client(&scratch), &mut guesses, 4, Part::One, "528").unwrap();
+   assert_eq!(response, Response::Wait(std::time::Duration::from_secs(65)));
+   assert_eq!(guesses.rule_out(4, Part::One, "528"), None);
+}